@@ -1,23 +1,4 @@
-// Import all local modules
-mod color;
-mod game_window;
-mod matrix;
-mod mesh;
-mod screen;
-mod transform;
-mod triangle;
-mod vector;
-
-// Make all module exports visible
-pub use color::*;
-pub use game_window::*;
-pub use matrix::*;
-pub use mesh::*;
-pub use screen::*;
-pub use transform::*;
-pub use triangle::*;
-pub use vector::*;
-
+use render_3d::*;
 use std::time::Instant;
 
 /// Count frames per second.
@@ -68,35 +49,20 @@ fn main() {
         width: 800,
         height: 600,
         shadow_intensity: 0.5,
+        depth_compare: DepthCompare::Less,
         ticks_per_second: 30,
         ..Default::default()
     })
     .unwrap();
 
-    // A mesh of a cube
-    let cube: Mesh<3, f32> = Mesh::from(vec![
-        // South face
-        ([0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 1.0, 0.0]),
-        ([0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [1.0, 0.0, 0.0]),
-        // East face
-        ([1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [1.0, 1.0, 1.0]),
-        ([1.0, 0.0, 0.0], [1.0, 1.0, 1.0], [1.0, 0.0, 1.0]),
-        // North face
-        ([1.0, 0.0, 1.0], [1.0, 1.0, 1.0], [0.0, 1.0, 1.0]),
-        ([1.0, 0.0, 1.0], [0.0, 1.0, 1.0], [0.0, 0.0, 1.0]),
-        // West face
-        ([0.0, 0.0, 1.0], [0.0, 1.0, 1.0], [0.0, 1.0, 0.0]),
-        ([0.0, 0.0, 1.0], [0.0, 1.0, 0.0], [0.0, 0.0, 0.0]),
-        // Top face
-        ([0.0, 1.0, 0.0], [0.0, 1.0, 1.0], [1.0, 1.0, 1.0]),
-        ([0.0, 1.0, 0.0], [1.0, 1.0, 1.0], [1.0, 1.0, 0.0]),
-        // Bottom face
-        ([1.0, 0.0, 1.0], [0.0, 0.0, 1.0], [0.0, 0.0, 0.0]),
-        ([1.0, 0.0, 1.0], [0.0, 0.0, 0.0], [1.0, 0.0, 0.0]),
-    ]);
-
-    // The camera
-    let camera = Vector::from([0.0, 0.0, -1.0]);
+    // A mesh of a unit cube
+    let cube = Mesh::cuboid([1.0, 1.0, 1.0], [1, 1, 1]);
+
+    // The center of the cube, about which it spins
+    let center = cube.centroid();
+
+    // Move the camera back from the origin
+    window.camera_mut().set_position([0.0, 0.0, -1.0]);
 
     // The light
     let light = Vector::from([0.0, 0.0, -1.0]).normalize();
@@ -111,15 +77,17 @@ fn main() {
     while window.open() {
         // Fill background with dark blue
         window.fill((0, 0, 127));
+        window.clear_depth();
 
         // Transform and project the cube
         let cube_projected = window
             .mesh_transformation(&cube)
-            // .translate([-0.5, -0.5, -0.5])
-            .rotate_z(theta)
-            .rotate_x(0.5 * theta)
-            .translate([0.0, 0.0, 2.0])
-            .normalize_filter(&camera)
+            .rotate_about(center, [0.0, 0.0, 1.0], theta)
+            .rotate_about(center, [1.0, 0.0, 0.0], 0.5 * theta)
+            .translate([0.5, 0.5, 2.5])
+            .view()
+            .clip_near()
+            .normalize_filter()
             .apply_luminance(&light)
             .project()
             .translate([1.0, 1.0])
@@ -0,0 +1,338 @@
+use crate::matrix::*;
+use crate::quaternion::*;
+use crate::vector::*;
+
+/// Generate an orthographic projection matrix.
+///
+/// `aspect_ratio`: the screen aspect ratio.
+/// `height`: the height of the view volume.
+/// `near`: the view near factor.
+/// `far`: the view far factor.
+///
+/// Returns the generated 4x4 projection matrix.
+fn generate_orthographic_matrix(
+    aspect_ratio: f32,
+    height: f32,
+    near: f32,
+    far: f32,
+) -> Matrix<4, 4, f32> {
+    let mut projection_matrix = Matrix::<4, 4, f32>::fill(0.0);
+
+    projection_matrix[(0, 0)] = 2.0 * aspect_ratio / height;
+    projection_matrix[(1, 1)] = 2.0 / height;
+    projection_matrix[(2, 2)] = 1.0 / (far - near);
+    projection_matrix[(3, 2)] = -near / (far - near);
+    projection_matrix[(3, 3)] = 1.0;
+
+    projection_matrix
+}
+
+/// Generate a perspective projection matrix.
+///
+/// `aspect_ratio`: the screen aspect ratio.
+/// `fov_rad`: the field of view in radians.
+/// `near`: the view near factor.
+/// `far`: the view far factor.
+///
+/// Returns the generated 4x4 projection matrix.
+fn generate_projection_matrix(
+    aspect_ratio: f32,
+    fov_rad: f32,
+    near: f32,
+    far: f32,
+) -> Matrix<4, 4, f32> {
+    let mut projection_matrix = Matrix::<4, 4, f32>::fill(0.0);
+
+    projection_matrix[(0, 0)] = aspect_ratio * fov_rad;
+    projection_matrix[(1, 1)] = fov_rad;
+    projection_matrix[(2, 2)] = far / (far - near);
+    projection_matrix[(3, 2)] = (-far * near) / (far - near);
+    projection_matrix[(2, 3)] = 1.0;
+
+    projection_matrix
+}
+
+/// The kind of projection a camera uses.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Projection {
+    /// Perspective projection, where distant objects appear smaller, using the camera's field of view.
+    #[default]
+    Perspective,
+    /// Orthographic projection, where objects appear the same size at any distance.
+    Orthographic {
+        /// The height of the view volume in world units. Its width follows from the screen aspect ratio.
+        height: f32,
+    },
+}
+
+/// A camera with a position, an orientation and view parameters.
+///
+/// The camera looks along its forward vector, which points down the positive z-axis by default, with its up vector along the positive y-axis.
+#[derive(Clone, Copy, Debug)]
+pub struct Camera {
+    /// The position of the camera.
+    position: Vector<3>,
+    /// The normalized direction the camera is facing.
+    forward: Vector<3>,
+    /// The normalized up direction of the camera, perpendicular to the forward direction.
+    up: Vector<3>,
+    /// The kind of projection the camera uses.
+    projection: Projection,
+    /// The field of view in degrees, used for perspective projection.
+    fov: f32,
+    /// The view near factor.
+    near: f32,
+    /// The view far factor.
+    far: f32,
+}
+
+// Camera implementation
+impl Camera {
+    /// Create a new camera facing down the positive z-axis.
+    ///
+    /// `position`: the position of the camera.
+    /// `fov`: the field of view in degrees.
+    /// `near`: the view near factor.
+    /// `far`: the view far factor.
+    ///
+    /// Returns the new camera.
+    pub fn new<V: Into<Vector<3>>>(position: V, fov: f32, near: f32, far: f32) -> Self {
+        Self {
+            position: position.into(),
+            forward: Vector::from([0.0, 0.0, 1.0]),
+            up: Vector::from([0.0, 1.0, 0.0]),
+            projection: Projection::Perspective,
+            fov,
+            near,
+            far,
+        }
+    }
+
+    /// Create a new orthographic camera with an isometric view of a point. The camera looks down at the point from the negative y side, rotated 45 degrees around the y-axis, so that all three axes appear equally foreshortened.
+    ///
+    /// `target`: the point the camera looks at.
+    /// `distance`: the distance of the camera from the point.
+    /// `height`: the height of the view volume in world units.
+    ///
+    /// Returns the new camera.
+    pub fn isometric<V: Into<Vector<3>>>(target: V, distance: f32, height: f32) -> Self {
+        Self::axonometric(target.into(), distance, height, (0.5f32).sqrt().atan())
+    }
+
+    /// Create a new orthographic camera with a 2:1 dimetric view of a point, as commonly used for pixel art tiles. The camera looks down at the point from the negative y side, rotated 45 degrees around the y-axis.
+    ///
+    /// `target`: the point the camera looks at.
+    /// `distance`: the distance of the camera from the point.
+    /// `height`: the height of the view volume in world units.
+    ///
+    /// Returns the new camera.
+    pub fn dimetric<V: Into<Vector<3>>>(target: V, distance: f32, height: f32) -> Self {
+        Self::axonometric(target.into(), distance, height, (0.5f32).atan())
+    }
+
+    /// Create a new orthographic camera rotated 45 degrees around the y-axis and tilted to look down at a point.
+    ///
+    /// `target`: the point the camera looks at.
+    /// `distance`: the distance of the camera from the point.
+    /// `height`: the height of the view volume in world units.
+    /// `pitch`: the downward tilt of the camera in radians.
+    ///
+    /// Returns the new camera.
+    fn axonometric(target: Vector<3>, distance: f32, height: f32, pitch: f32) -> Self {
+        let mut camera = Self::default();
+
+        camera.set_projection(Projection::Orthographic { height });
+        camera.set_rotation(std::f32::consts::FRAC_PI_4, pitch, 0.0);
+        camera.set_position(target - camera.forward * distance);
+
+        camera
+    }
+
+    /// Get the position of the camera.
+    ///
+    /// Returns the camera's position.
+    pub fn get_position(&self) -> Vector<3> {
+        self.position
+    }
+
+    /// Set the position of the camera.
+    ///
+    /// `position`: the new position of the camera.
+    pub fn set_position<V: Into<Vector<3>>>(&mut self, position: V) {
+        self.position = position.into();
+    }
+
+    /// Get the direction the camera is facing.
+    ///
+    /// Returns the camera's normalized forward vector.
+    pub fn get_forward(&self) -> Vector<3> {
+        self.forward
+    }
+
+    /// Get the up direction of the camera.
+    ///
+    /// Returns the camera's normalized up vector.
+    pub fn get_up(&self) -> Vector<3> {
+        self.up
+    }
+
+    /// Get the right direction of the camera.
+    ///
+    /// Returns the camera's normalized right vector.
+    pub fn get_right(&self) -> Vector<3> {
+        self.up.cross(&self.forward).normalize()
+    }
+
+    /// Set the orientation of the camera from forward and up vectors. The up vector does not need to be perpendicular to the forward vector, but must not be parallel to it.
+    ///
+    /// `forward`: the direction the camera should face.
+    /// `up`: the approximate up direction of the camera.
+    pub fn set_orientation<V: Into<Vector<3>>>(&mut self, forward: V, up: V) {
+        let forward = forward.into().normalize();
+        let right = up.into().cross(&forward).normalize();
+
+        self.forward = forward;
+        self.up = forward.cross(&right);
+    }
+
+    /// Set the orientation of the camera from Euler angles, applied in the order roll, pitch, yaw.
+    ///
+    /// `yaw`: the angle of rotation in radians around the y-axis, turning the camera toward the positive x-axis.
+    /// `pitch`: the angle of rotation in radians around the camera's right axis, tilting the camera toward the positive y-axis.
+    /// `roll`: the angle of rotation in radians around the camera's forward axis.
+    pub fn set_rotation(&mut self, yaw: f32, pitch: f32, roll: f32) {
+        let forward = Vector::from([
+            yaw.sin() * pitch.cos(),
+            pitch.sin(),
+            yaw.cos() * pitch.cos(),
+        ]);
+        let right = Vector::from([yaw.cos(), 0.0, -yaw.sin()]);
+        let up = forward.cross(&right);
+
+        self.forward = forward;
+        self.up = up * roll.cos() - right * roll.sin();
+    }
+
+    /// Set the orientation of the camera from a rotation quaternion, applied to the default orientation facing down the positive z-axis.
+    ///
+    /// `rotation`: the rotation quaternion, which should be normalized.
+    pub fn set_rotation_quaternion(&mut self, rotation: &Quaternion) {
+        self.forward = rotation.rotate_vector([0.0, 0.0, 1.0]).normalize();
+        self.up = rotation.rotate_vector([0.0, 1.0, 0.0]).normalize();
+    }
+
+    /// Rotate the camera from its current orientation by a rotation quaternion.
+    ///
+    /// `rotation`: the rotation quaternion, which should be normalized.
+    pub fn rotate(&mut self, rotation: &Quaternion) {
+        self.forward = rotation.rotate_vector(self.forward).normalize();
+        self.up = rotation.rotate_vector(self.up).normalize();
+    }
+
+    /// Turn the camera to face a point, keeping the current up direction as a reference.
+    ///
+    /// `target`: the point the camera should face.
+    pub fn look_at<V: Into<Vector<3>>>(&mut self, target: V) {
+        let forward = target.into() - self.position;
+        self.set_orientation(forward, self.up);
+    }
+
+    /// Get the kind of projection the camera uses.
+    ///
+    /// Returns the camera's projection.
+    pub fn get_projection(&self) -> Projection {
+        self.projection
+    }
+
+    /// Set the kind of projection the camera uses.
+    ///
+    /// `projection`: the new projection.
+    pub fn set_projection(&mut self, projection: Projection) {
+        self.projection = projection;
+    }
+
+    /// Get the field of view.
+    ///
+    /// Returns the field of view in degrees.
+    pub fn get_fov(&self) -> f32 {
+        self.fov
+    }
+
+    /// Set the field of view. This only affects perspective projection.
+    ///
+    /// `fov`: the new field of view in degrees.
+    pub fn set_fov(&mut self, fov: f32) {
+        self.fov = fov;
+    }
+
+    /// Get the view near factor.
+    ///
+    /// Returns the near factor.
+    pub fn get_near(&self) -> f32 {
+        self.near
+    }
+
+    /// Set the view near factor.
+    ///
+    /// `near`: the new near factor.
+    pub fn set_near(&mut self, near: f32) {
+        self.near = near;
+    }
+
+    /// Get the view far factor.
+    ///
+    /// Returns the far factor.
+    pub fn get_far(&self) -> f32 {
+        self.far
+    }
+
+    /// Set the view far factor.
+    ///
+    /// `far`: the new far factor.
+    pub fn set_far(&mut self, far: f32) {
+        self.far = far;
+    }
+
+    /// Generate the view matrix for the camera, moving points from world space into view space. In view space the camera sits at the origin and faces down the positive z-axis.
+    ///
+    /// Returns the 4x4 look-at view matrix.
+    pub fn view_matrix(&self) -> Matrix<4, 4, f32> {
+        let right = self.get_right();
+        let mut view_matrix = Matrix::<4, 4, f32>::identity();
+
+        for (row, axis) in [right, self.up, self.forward].iter().enumerate() {
+            for col in 0..3 {
+                view_matrix[(col, row)] = axis[col];
+            }
+
+            view_matrix[(3, row)] = -axis.dot(&self.position);
+        }
+
+        view_matrix
+    }
+
+    /// Generate the projection matrix for the camera.
+    ///
+    /// `aspect_ratio`: the screen aspect ratio, as height divided by width.
+    ///
+    /// Returns the 4x4 projection matrix.
+    pub fn projection_matrix(&self, aspect_ratio: f32) -> Matrix<4, 4, f32> {
+        match self.projection {
+            Projection::Perspective => {
+                let fov_rad = 1.0 / ((self.fov / 2.0).to_radians()).tan();
+
+                generate_projection_matrix(aspect_ratio, fov_rad, self.near, self.far)
+            }
+            Projection::Orthographic { height } => {
+                generate_orthographic_matrix(aspect_ratio, height, self.near, self.far)
+            }
+        }
+    }
+}
+
+// Default camera at the origin
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new([0.0, 0.0, 0.0], 90.0, 0.1, 1000.0)
+    }
+}
@@ -0,0 +1,50 @@
+/// Clip a convex polygon against a plane using the Sutherland–Hodgman algorithm. The winding order of the polygon is preserved.
+///
+/// `polygon`: the vertices of the polygon.
+/// `distance`: a closure computing the signed distance of a vertex from the plane, where vertices with a non-negative distance are kept.
+/// `lerp`: a closure interpolating between two vertices by a factor in the range [0, 1].
+///
+/// Returns the vertices of the clipped polygon, which will be empty if the polygon lies entirely outside the plane.
+pub(crate) fn clip_polygon<V, D, L>(polygon: &[V], distance: D, lerp: L) -> Vec<V>
+where
+    V: Clone,
+    D: Fn(&V) -> f32,
+    L: Fn(&V, &V, f32) -> V,
+{
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+
+    for (i, current) in polygon.iter().enumerate() {
+        let next = &polygon[(i + 1) % polygon.len()];
+        let current_distance = distance(current);
+        let next_distance = distance(next);
+
+        if current_distance >= 0.0 {
+            clipped.push(current.clone());
+        }
+
+        // Add the intersection point if the edge crosses the plane
+        if (current_distance >= 0.0) != (next_distance >= 0.0) {
+            let t = current_distance / (current_distance - next_distance);
+            clipped.push(lerp(current, next, t));
+        }
+    }
+
+    clipped
+}
+
+/// Split a convex polygon into a fan of triangles sharing its first vertex. The winding order of the polygon is preserved.
+///
+/// `polygon`: the vertices of the polygon.
+///
+/// Returns the vertices of each triangle in the fan.
+pub(crate) fn triangulate_fan<V: Clone>(polygon: &[V]) -> Vec<[V; 3]> {
+    (1..polygon.len().saturating_sub(1))
+        .map(|i| {
+            [
+                polygon[0].clone(),
+                polygon[i].clone(),
+                polygon[i + 1].clone(),
+            ]
+        })
+        .collect()
+}
@@ -1,7 +1,7 @@
 use std::ops::{Index, IndexMut};
 
 /// A representation of a color.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Color(u8, u8, u8);
 
 // Convert a 3-tuple into a color
@@ -12,9 +12,9 @@ impl From<(u8, u8, u8)> for Color {
 }
 
 // Convert a color into a 3-tuple
-impl Into<(u8, u8, u8)> for Color {
-    fn into(self) -> (u8, u8, u8) {
-        (self.0, self.1, self.2)
+impl From<Color> for (u8, u8, u8) {
+    fn from(color: Color) -> Self {
+        (color.0, color.1, color.2)
     }
 }
 
@@ -26,9 +26,9 @@ impl From<u32> for Color {
 }
 
 // Convert a color into an unsigned 32-bit integer
-impl Into<u32> for Color {
-    fn into(self) -> u32 {
-        self.to_u8_rgb()
+impl From<Color> for u32 {
+    fn from(color: Color) -> Self {
+        color.to_u8_rgb()
     }
 }
 
@@ -97,7 +97,7 @@ impl Index<usize> for Color {
     type Output = u8;
 
     fn index(&self, index: usize) -> &Self::Output {
-        &self.get(index).unwrap()
+        self.get(index).unwrap()
     }
 }
 
@@ -0,0 +1,148 @@
+use std::ops::{Index, IndexMut};
+
+/// A comparison function used to decide whether a fragment passes the depth test.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DepthCompare {
+    /// The depth test never passes.
+    Never,
+    /// Passes if the new depth is less than the stored depth.
+    Less,
+    /// Passes if the new depth is less than or equal to the stored depth.
+    LessEqual,
+    /// Passes if the new depth is equal to the stored depth.
+    Equal,
+    /// Passes if the new depth is not equal to the stored depth.
+    NotEqual,
+    /// Passes if the new depth is greater than the stored depth.
+    Greater,
+    /// Passes if the new depth is greater than or equal to the stored depth.
+    GreaterEqual,
+    /// The depth test always passes.
+    #[default]
+    Always,
+}
+
+// Depth comparison implementation
+impl DepthCompare {
+    /// Perform the depth test.
+    ///
+    /// `depth`: the depth of the new fragment.
+    /// `stored`: the depth currently stored in the depth buffer.
+    ///
+    /// Returns whether the new fragment passes the test.
+    pub fn test(&self, depth: f32, stored: f32) -> bool {
+        match self {
+            Self::Never => false,
+            Self::Less => depth < stored,
+            Self::LessEqual => depth <= stored,
+            Self::Equal => depth == stored,
+            Self::NotEqual => depth != stored,
+            Self::Greater => depth > stored,
+            Self::GreaterEqual => depth >= stored,
+            Self::Always => true,
+        }
+    }
+
+    /// Get the value a depth buffer should be cleared to before drawing with this comparison. This is the farthest possible depth, infinity for `Less` and `LessEqual` or negative infinity for `Greater` and `GreaterEqual`, so that the first fragment drawn to each pixel passes. The remaining comparisons do not depend on a stored nearest depth, and use infinity.
+    ///
+    /// Returns the clear value.
+    pub fn clear_value(&self) -> f32 {
+        match self {
+            Self::Greater | Self::GreaterEqual => f32::NEG_INFINITY,
+            _ => f32::INFINITY,
+        }
+    }
+}
+
+/// Per-pixel depth buffer abstraction.
+#[derive(Clone, Debug)]
+pub struct DepthBuffer {
+    /// The buffer width in pixels.
+    width: usize,
+    /// The buffer height in pixels.
+    height: usize,
+    /// The depth values, one per pixel.
+    buffer: Vec<f32>,
+}
+
+// Depth buffer implementation
+impl DepthBuffer {
+    /// Create a new depth buffer.
+    ///
+    /// `width`: the width of the buffer in pixels.
+    /// `height`: the height of the buffer in pixels.
+    ///
+    /// Returns the new depth buffer, filled with positive infinity.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            buffer: vec![f32::INFINITY; width * height],
+        }
+    }
+
+    /// Get the width of the depth buffer.
+    ///
+    /// Returns the width of the buffer in pixels.
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height of the depth buffer.
+    ///
+    /// Returns the height of the buffer in pixels.
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    /// Fill the depth buffer with a single value.
+    ///
+    /// `value`: the depth value with which to fill the buffer.
+    pub fn clear(&mut self, value: f32) {
+        self.buffer.fill(value);
+    }
+
+    /// Get a reference to a depth value in the buffer.
+    ///
+    /// `x`: the x coordinate of the pixel.
+    /// `y`: the y coordinate of the pixel.
+    ///
+    /// Returns an option containing a reference to the depth value, or the none variant if the (`x`, `y`) index is invalid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&f32> {
+        if x < self.width && y < self.height {
+            self.buffer.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to a depth value in the buffer.
+    ///
+    /// `x`: the x coordinate of the pixel.
+    /// `y`: the y coordinate of the pixel.
+    ///
+    /// Returns an option containing a mutable reference to the depth value, or the none variant if the (`x`, `y`) index is invalid.
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut f32> {
+        if x < self.width && y < self.height {
+            self.buffer.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+}
+
+// Indexing operations for depth buffers
+impl Index<(usize, usize)> for DepthBuffer {
+    type Output = f32;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        self.get(index.0, index.1).unwrap()
+    }
+}
+
+// Mutable indexing operations for depth buffers
+impl IndexMut<(usize, usize)> for DepthBuffer {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        self.get_mut(index.0, index.1).unwrap()
+    }
+}
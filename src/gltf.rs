@@ -0,0 +1,899 @@
+use crate::color::*;
+use crate::json::*;
+use crate::matrix::*;
+use crate::mesh::*;
+use crate::quaternion::*;
+use crate::triangle::*;
+use crate::vector::*;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// The magic number at the start of a binary glTF file, `glTF` in little endian.
+const GLB_MAGIC: u32 = 0x4654_6c67;
+
+/// The chunk type of the JSON chunk in a binary glTF file.
+const GLB_JSON_CHUNK: u32 = 0x4e4f_534a;
+
+/// The chunk type of the binary buffer chunk in a binary glTF file.
+const GLB_BIN_CHUNK: u32 = 0x004e_4942;
+
+/// The primitive mode for separate triangles.
+const MODE_TRIANGLES: usize = 4;
+
+/// The primitive mode for a triangle strip.
+const MODE_TRIANGLE_STRIP: usize = 5;
+
+/// The primitive mode for a triangle fan.
+const MODE_TRIANGLE_FAN: usize = 6;
+
+/// The largest number of components read from an accessor without a buffer view. Such accessors are filled with zeros, so their size is not limited by any buffer.
+const MAX_UNBACKED_COMPONENTS: usize = 1 << 24;
+
+/// An error type for loading glTF files.
+#[derive(Debug)]
+pub enum GltfError {
+    /// An error reading a file.
+    IOError(io::Error),
+    /// Malformed JSON in the glTF document.
+    ParseError {
+        /// The line number of the problem, starting from one.
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+    /// A document or binary data that does not follow the glTF specification, or uses an unsupported feature.
+    InvalidData(String),
+}
+
+// Convert I/O errors to glTF errors
+impl From<io::Error> for GltfError {
+    fn from(e: io::Error) -> Self {
+        Self::IOError(e)
+    }
+}
+
+// Convert JSON errors to glTF errors
+impl From<JsonError> for GltfError {
+    fn from(e: JsonError) -> Self {
+        Self::ParseError {
+            line: e.line,
+            message: e.message,
+        }
+    }
+}
+
+// Display glTF errors
+impl fmt::Display for GltfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IOError(e) => write!(f, "{}", e),
+            Self::ParseError { line, message } => write!(f, "line {}: {}", line, message),
+            Self::InvalidData(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// Mark glTF error as an implementation of the standard error trait
+impl error::Error for GltfError {}
+
+/// A result type for loading glTF files.
+pub type GltfResult<T> = Result<T, GltfError>;
+
+/// Create an invalid data error.
+///
+/// `message`: a description of the problem.
+///
+/// Returns the error.
+fn invalid<S: Into<String>>(message: S) -> GltfError {
+    GltfError::InvalidData(message.into())
+}
+
+/// Decode standard base64 text, with or without padding.
+///
+/// `text`: the base64 text.
+///
+/// Returns an option containing the decoded bytes, or the none variant if the text is not valid base64.
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=').as_bytes();
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut bits = 0u32;
+    let mut bit_count = 0;
+
+    for &character in text {
+        let value = match character {
+            b'A'..=b'Z' => character - b'A',
+            b'a'..=b'z' => character - b'a' + 26,
+            b'0'..=b'9' => character - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+
+        bits = (bits << 6) | value as u32;
+        bit_count += 6;
+
+        if bit_count >= 8 {
+            bit_count -= 8;
+            bytes.push((bits >> bit_count) as u8);
+        }
+    }
+
+    Some(bytes)
+}
+
+/// Read a little endian 32-bit unsigned integer.
+///
+/// `data`: the data to read from.
+/// `offset`: the offset of the integer.
+///
+/// Returns an option containing the integer, or the none variant if the data is too short.
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+
+    Some(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// Read a list of numbers from a JSON array.
+///
+/// `value`: the JSON array.
+///
+/// Returns an option containing the numbers, or the none variant if the value is not an array of exactly `N` numbers.
+fn read_numbers<const N: usize>(value: &JsonValue) -> Option<[f32; N]> {
+    let items = value.as_array().filter(|items| items.len() == N)?;
+    let mut numbers = [0.0; N];
+
+    for (number, item) in numbers.iter_mut().zip(items) {
+        *number = item.as_f64()? as f32;
+    }
+
+    Some(numbers)
+}
+
+/// Calculate the local transform of a node, from either its matrix or its translation, rotation and scale.
+///
+/// `node`: the node.
+///
+/// Returns a result containing the local transform.
+fn node_transform(node: &JsonValue) -> GltfResult<Matrix<4, 4, f32>> {
+    let property = |name: &str| node.get(name).filter(|value| **value != JsonValue::Null);
+
+    if let Some(matrix) = property("matrix") {
+        let values = read_numbers::<16>(matrix).ok_or_else(|| invalid("invalid node matrix"))?;
+
+        // Matrices are stored in column-major order
+        let mut rows = [[0.0; 4]; 4];
+
+        for (i, value) in values.iter().enumerate() {
+            rows[i % 4][i / 4] = *value;
+        }
+
+        return Ok(Matrix::from_rows(rows));
+    }
+
+    let [tx, ty, tz] = match property("translation") {
+        Some(value) => read_numbers(value).ok_or_else(|| invalid("invalid node translation"))?,
+        None => [0.0; 3],
+    };
+    let [x, y, z, w] = match property("rotation") {
+        Some(value) => read_numbers(value).ok_or_else(|| invalid("invalid node rotation"))?,
+        None => [0.0, 0.0, 0.0, 1.0],
+    };
+    let [sx, sy, sz] = match property("scale") {
+        Some(value) => read_numbers(value).ok_or_else(|| invalid("invalid node scale"))?,
+        None => [1.0; 3],
+    };
+
+    let translation = Matrix::from_rows([
+        [1.0, 0.0, 0.0, tx],
+        [0.0, 1.0, 0.0, ty],
+        [0.0, 0.0, 1.0, tz],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+    let rotation = Quaternion::new(w, x, y, z).normalize().to_matrix();
+    let scale = Matrix::from_rows([
+        [sx, 0.0, 0.0, 0.0],
+        [0.0, sy, 0.0, 0.0],
+        [0.0, 0.0, sz, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+
+    Ok(translation * rotation * scale)
+}
+
+/// A mesh primitive from a glTF scene, placed by the node that references it.
+#[derive(Clone, Debug)]
+pub struct GltfMesh {
+    /// The name of the node referencing the mesh, if any.
+    pub node: Option<String>,
+    /// The name of the mesh, if any.
+    pub name: Option<String>,
+    /// The triangles of the primitive in the mesh's local space.
+    pub mesh: Mesh<3, f32>,
+    /// The vertex normals of each triangle in the mesh's local space, if the primitive has normals.
+    pub normals: Vec<Option<[Vector<3>; 3]>>,
+    /// The texture coordinates of each triangle, if the primitive has texture coordinates.
+    pub uvs: Vec<Option<[Vector<2>; 3]>>,
+    /// The name of the primitive's material, if any.
+    pub material: Option<String>,
+    /// The base color factor of the primitive's material as red, green, blue and alpha from zero to one.
+    pub base_color: [f32; 4],
+    /// The world transform of the node, taking the mesh from local space into world space.
+    pub transform: Matrix<4, 4, f32>,
+}
+
+// glTF mesh implementation
+impl GltfMesh {
+    /// Get the base color of the primitive's material.
+    ///
+    /// Returns the base color, ignoring alpha.
+    pub fn color(&self) -> Color {
+        let [r, g, b, _] = self
+            .base_color
+            .map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8);
+
+        Color::new(r, g, b)
+    }
+
+    /// Get the triangles of the primitive in world space. Vertex normals are transformed by the normal matrix of the node's transform, so they stay perpendicular to the surface.
+    ///
+    /// Returns the transformed mesh.
+    pub fn world_mesh(&self) -> Mesh<3, f32> {
+        let mut linear = Matrix::<3, 3, f32>::new();
+
+        for x in 0..3 {
+            for y in 0..3 {
+                linear[(x, y)] = self.transform[(x, y)];
+            }
+        }
+
+        let normal_matrix = linear.normal_matrix();
+
+        self.mesh.transform(|triangle| {
+            triangle
+                .transform(|vector| self.transform * *vector)
+                .transform_normals(|normal| (normal_matrix * *normal).normalize())
+        })
+    }
+}
+
+/// A parsed glTF document along with its loaded buffers.
+struct Document {
+    /// The JSON document.
+    json: JsonValue,
+    /// The contents of each buffer.
+    buffers: Vec<Vec<u8>>,
+}
+
+// glTF document implementation
+impl Document {
+    /// Get a top-level array of the document.
+    ///
+    /// `name`: the name of the array.
+    ///
+    /// Returns the items of the array, which are empty if it is missing.
+    fn array(&self, name: &str) -> &[JsonValue] {
+        self.json
+            .get(name)
+            .and_then(|value| value.as_array())
+            .unwrap_or(&[])
+    }
+
+    /// Get an item of a top-level array of the document.
+    ///
+    /// `name`: the name of the array.
+    /// `index`: the index of the item.
+    ///
+    /// Returns a result containing the item, or the error variant if the index is out of range.
+    fn item(&self, name: &str, index: usize) -> GltfResult<&JsonValue> {
+        self.array(name)
+            .get(index)
+            .ok_or_else(|| invalid(format!("{} index {} out of range", name, index)))
+    }
+
+    /// Read the elements of an accessor, converting each component to a floating point value.
+    ///
+    /// `index`: the index of the accessor.
+    ///
+    /// Returns a result containing the components of every element in order, along with the number of components per element.
+    fn accessor(&self, index: usize) -> GltfResult<(Vec<f32>, usize)> {
+        let accessor = self.item("accessors", index)?;
+        let components = match accessor.get("type").and_then(|kind| kind.as_str()) {
+            Some("SCALAR") => 1,
+            Some("VEC2") => 2,
+            Some("VEC3") => 3,
+            Some("VEC4") | Some("MAT2") => 4,
+            Some("MAT3") => 9,
+            Some("MAT4") => 16,
+            _ => return Err(invalid(format!("accessor {} has an invalid type", index))),
+        };
+        let component_type = accessor
+            .get("componentType")
+            .and_then(|kind| kind.as_usize())
+            .unwrap_or(0);
+        let normalized = accessor.get("normalized") == Some(&JsonValue::Bool(true));
+
+        let size = match component_type {
+            5120 | 5121 => 1,
+            5122 | 5123 => 2,
+            5125 | 5126 => 4,
+            _ => {
+                return Err(invalid(format!(
+                    "accessor {} has an invalid component type",
+                    index
+                )))
+            }
+        };
+
+        let decode = |bytes: &[u8]| -> f32 {
+            let (value, maximum) = match component_type {
+                5120 => (bytes[0] as i8 as f32, i8::MAX as f32),
+                5121 => (bytes[0] as f32, u8::MAX as f32),
+                5122 => (
+                    i16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+                    i16::MAX as f32,
+                ),
+                5123 => (
+                    u16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+                    u16::MAX as f32,
+                ),
+                5125 => (u32::from_le_bytes(bytes.try_into().unwrap()) as f32, 1.0),
+                _ => return f32::from_le_bytes(bytes.try_into().unwrap()),
+            };
+
+            // Normalized integers map onto zero to one, or minus one to one if signed
+            if normalized {
+                (value / maximum).max(-1.0)
+            } else {
+                value
+            }
+        };
+
+        Ok((
+            self.read_accessor(index, components, size, decode)?,
+            components,
+        ))
+    }
+
+    /// Read the elements of an accessor used as vertex indices. Only unsigned integer scalars are valid indices.
+    ///
+    /// `index`: the index of the accessor.
+    ///
+    /// Returns a result containing the vertex indices.
+    fn indices(&self, index: usize) -> GltfResult<Vec<usize>> {
+        let accessor = self.item("accessors", index)?;
+
+        if accessor.get("type").and_then(|kind| kind.as_str()) != Some("SCALAR")
+            || accessor.get("normalized") == Some(&JsonValue::Bool(true))
+        {
+            return Err(invalid(format!(
+                "accessor {} is not a valid index accessor",
+                index
+            )));
+        }
+
+        let component_type = accessor
+            .get("componentType")
+            .and_then(|kind| kind.as_usize());
+        let size = match component_type {
+            Some(5121) => 1,
+            Some(5123) => 2,
+            Some(5125) => 4,
+            _ => {
+                return Err(invalid(format!(
+                    "accessor {} has an invalid index component type",
+                    index
+                )))
+            }
+        };
+
+        self.read_accessor(index, 1, size, |bytes: &[u8]| match bytes {
+            [byte] => *byte as usize,
+            [low, high] => u16::from_le_bytes([*low, *high]) as usize,
+            _ => u32::from_le_bytes(bytes.try_into().unwrap()) as usize,
+        })
+    }
+
+    /// Read and decode every component of an accessor from its buffer view.
+    ///
+    /// `index`: the index of the accessor.
+    /// `components`: the number of components per element.
+    /// `size`: the size of each component in bytes.
+    /// `decode`: the closure used to decode the bytes of each component.
+    ///
+    /// Returns a result containing the components of every element in order.
+    fn read_accessor<V: Clone + Default, F: Fn(&[u8]) -> V>(
+        &self,
+        index: usize,
+        components: usize,
+        size: usize,
+        decode: F,
+    ) -> GltfResult<Vec<V>> {
+        let accessor = self.item("accessors", index)?;
+
+        if accessor.get("sparse").is_some() {
+            return Err(invalid("sparse accessors are not supported"));
+        }
+
+        let count = accessor
+            .get("count")
+            .and_then(|count| count.as_usize())
+            .ok_or_else(|| invalid(format!("accessor {} has no count", index)))?;
+        let length = count
+            .checked_mul(components)
+            .ok_or_else(|| invalid(format!("accessor {} has too many elements", index)))?;
+
+        // Accessors without a buffer view are initialized to zeros
+        let view_index = match accessor.get("bufferView").and_then(|view| view.as_usize()) {
+            Some(view_index) => view_index,
+            None if length <= MAX_UNBACKED_COMPONENTS => return Ok(vec![V::default(); length]),
+            None => {
+                return Err(invalid(format!(
+                    "accessor {} has too many elements without a buffer view",
+                    index
+                )))
+            }
+        };
+
+        let view = self.item("bufferViews", view_index)?;
+        let buffer_index = view
+            .get("buffer")
+            .and_then(|buffer| buffer.as_usize())
+            .ok_or_else(|| invalid(format!("buffer view {} has no buffer", view_index)))?;
+        let buffer = self
+            .buffers
+            .get(buffer_index)
+            .ok_or_else(|| invalid(format!("buffers index {} out of range", buffer_index)))?;
+        let number = |value: &JsonValue, name: &str| {
+            value
+                .get(name)
+                .and_then(|number| number.as_usize())
+                .unwrap_or(0)
+        };
+
+        let view_offset = number(view, "byteOffset");
+        let view_length = number(view, "byteLength");
+        let element_size = size * components;
+        let stride = match number(view, "byteStride") {
+            0 => element_size,
+            stride => stride,
+        };
+        let offset = number(accessor, "byteOffset");
+
+        let end = match count {
+            0 => Some(offset),
+            count => stride
+                .checked_mul(count - 1)
+                .and_then(|span| span.checked_add(offset))
+                .and_then(|span| span.checked_add(element_size)),
+        };
+
+        // The range is validated before allocating, so the element count is limited by the buffer's size
+        let end = match (end, view_offset.checked_add(view_length)) {
+            (Some(end), Some(view_end)) if end <= view_length && view_end <= buffer.len() => end,
+            _ => {
+                return Err(invalid(format!(
+                    "accessor {} reads past the end of its buffer",
+                    index
+                )))
+            }
+        };
+
+        let data = &buffer[view_offset + offset..view_offset + end];
+        let mut values = vec![V::default(); length];
+
+        for (i, element) in values.chunks_exact_mut(components).enumerate() {
+            for (j, value) in element.iter_mut().enumerate() {
+                let start = i * stride + j * size;
+                *value = decode(&data[start..start + size]);
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Read the elements of an accessor as vectors.
+    ///
+    /// `index`: the index of the accessor.
+    ///
+    /// Returns a result containing the vectors, or the error variant if the accessor does not have `N` components.
+    fn vectors<const N: usize>(&self, index: usize) -> GltfResult<Vec<Vector<N>>> {
+        let (values, components) = self.accessor(index)?;
+
+        if components != N {
+            return Err(invalid(format!(
+                "accessor {} has {} components, expected {}",
+                index, components, N
+            )));
+        }
+
+        Ok(values
+            .chunks_exact(N)
+            .map(|chunk| Vector::from(<[f32; N]>::try_from(chunk).unwrap()))
+            .collect())
+    }
+
+    /// Read a mesh primitive.
+    ///
+    /// `mesh`: the mesh containing the primitive.
+    /// `primitive`: the primitive.
+    /// `node`: the name of the node referencing the mesh.
+    /// `transform`: the world transform of the node.
+    ///
+    /// Returns a result containing an option with the primitive, or the none variant if it is made of points or lines.
+    fn primitive(
+        &self,
+        mesh: &JsonValue,
+        primitive: &JsonValue,
+        node: Option<String>,
+        transform: Matrix<4, 4, f32>,
+    ) -> GltfResult<Option<GltfMesh>> {
+        let mode = primitive
+            .get("mode")
+            .and_then(|mode| mode.as_usize())
+            .unwrap_or(MODE_TRIANGLES);
+
+        if ![MODE_TRIANGLES, MODE_TRIANGLE_STRIP, MODE_TRIANGLE_FAN].contains(&mode) {
+            return Ok(None);
+        }
+
+        let attribute = |name: &str| {
+            primitive
+                .get("attributes")
+                .and_then(|attributes| attributes.get(name))
+                .and_then(|index| index.as_usize())
+        };
+
+        let positions = self.vectors::<3>(
+            attribute("POSITION").ok_or_else(|| invalid("primitive has no positions"))?,
+        )?;
+        let normals = attribute("NORMAL")
+            .map(|index| self.vectors::<3>(index))
+            .transpose()?;
+        let uvs = attribute("TEXCOORD_0")
+            .map(|index| self.vectors::<2>(index))
+            .transpose()?;
+
+        if normals
+            .as_ref()
+            .is_some_and(|normals| normals.len() != positions.len())
+            || uvs.as_ref().is_some_and(|uvs| uvs.len() != positions.len())
+        {
+            return Err(invalid("primitive attributes have different counts"));
+        }
+
+        let indices: Vec<usize> = match primitive.get("indices").and_then(|index| index.as_usize())
+        {
+            Some(index) => self.indices(index)?,
+            None => (0..positions.len()).collect(),
+        };
+
+        if let Some(index) = indices.iter().find(|&&index| index >= positions.len()) {
+            return Err(invalid(format!(
+                "vertex index {} out of range, only {} vertices",
+                index,
+                positions.len()
+            )));
+        }
+
+        let corners: Vec<[usize; 3]> = match mode {
+            MODE_TRIANGLE_STRIP => (0..indices.len().saturating_sub(2))
+                .map(|i| match i % 2 {
+                    0 => [indices[i], indices[i + 1], indices[i + 2]],
+                    _ => [indices[i], indices[i + 2], indices[i + 1]],
+                })
+                .collect(),
+            MODE_TRIANGLE_FAN => (1..indices.len().saturating_sub(1))
+                .map(|i| [indices[0], indices[i], indices[i + 1]])
+                .collect(),
+            _ => indices
+                .chunks_exact(3)
+                .map(|chunk| [chunk[0], chunk[1], chunk[2]])
+                .collect(),
+        };
+
+        let material = primitive
+            .get("material")
+            .and_then(|index| index.as_usize())
+            .map(|index| self.item("materials", index))
+            .transpose()?;
+        let base_color = material
+            .and_then(|material| material.get("pbrMetallicRoughness"))
+            .and_then(|pbr| pbr.get("baseColorFactor"))
+            .and_then(read_numbers::<4>)
+            .unwrap_or([1.0; 4]);
+        let name = |value: &JsonValue| {
+            value
+                .get("name")
+                .and_then(|name| name.as_str())
+                .map(|name| name.to_owned())
+        };
+
+        Ok(Some(GltfMesh {
+            node,
+            name: name(mesh),
+            mesh: corners
+                .iter()
+                .map(|corner| {
+                    let mut triangle = Triangle::new(corner.map(|i| positions[i]), 1.0);
+                    triangle
+                        .set_normals(normals.as_ref().map(|normals| corner.map(|i| normals[i])));
+                    triangle
+                })
+                .collect(),
+            normals: corners
+                .iter()
+                .map(|corner| normals.as_ref().map(|normals| corner.map(|i| normals[i])))
+                .collect(),
+            uvs: corners
+                .iter()
+                .map(|corner| uvs.as_ref().map(|uvs| corner.map(|i| uvs[i])))
+                .collect(),
+            material: material.and_then(name),
+            base_color,
+            transform,
+        }))
+    }
+
+    /// Visit a node and its descendants, collecting the mesh primitives they reference.
+    ///
+    /// `index`: the index of the node.
+    /// `parent`: the world transform of the node's parent.
+    /// `visited`: whether each node has already been visited, used to detect cycles and nodes with more than one parent.
+    /// `meshes`: the collected mesh primitives.
+    ///
+    /// Returns a result of the operation.
+    fn visit(
+        &self,
+        index: usize,
+        parent: Matrix<4, 4, f32>,
+        visited: &mut [bool],
+        meshes: &mut Vec<GltfMesh>,
+    ) -> GltfResult<()> {
+        let node = self.item("nodes", index)?;
+
+        // Each node may only have one parent, so reaching a node twice means the hierarchy is not a tree
+        if visited[index] {
+            return Err(invalid(format!(
+                "node {} is reached more than once in the hierarchy",
+                index
+            )));
+        }
+
+        visited[index] = true;
+        let transform = parent * node_transform(node)?;
+
+        if let Some(mesh_index) = node.get("mesh").and_then(|mesh| mesh.as_usize()) {
+            let mesh = self.item("meshes", mesh_index)?;
+            let name = node
+                .get("name")
+                .and_then(|name| name.as_str())
+                .map(|name| name.to_owned());
+
+            for primitive in mesh
+                .get("primitives")
+                .and_then(|primitives| primitives.as_array())
+                .unwrap_or(&[])
+            {
+                if let Some(primitive) = self.primitive(mesh, primitive, name.clone(), transform)? {
+                    meshes.push(primitive);
+                }
+            }
+        }
+
+        for child in node
+            .get("children")
+            .and_then(|children| children.as_array())
+            .unwrap_or(&[])
+        {
+            let child = child
+                .as_usize()
+                .ok_or_else(|| invalid("invalid child node index"))?;
+            self.visit(child, transform, visited, meshes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Find the root nodes of the default scene. If the document has no scenes, every node that is not a child of another node is a root.
+    ///
+    /// Returns a result containing the indices of the root nodes.
+    fn roots(&self) -> GltfResult<Vec<usize>> {
+        let indices = |value: Option<&JsonValue>| {
+            value
+                .and_then(|value| value.as_array())
+                .unwrap_or(&[])
+                .iter()
+                .map(|index| {
+                    index
+                        .as_usize()
+                        .ok_or_else(|| invalid("invalid node index"))
+                })
+                .collect::<GltfResult<Vec<_>>>()
+        };
+
+        if !self.array("scenes").is_empty() {
+            let scene = self
+                .json
+                .get("scene")
+                .and_then(|scene| scene.as_usize())
+                .unwrap_or(0);
+
+            return indices(self.item("scenes", scene)?.get("nodes"));
+        }
+
+        let mut children = Vec::new();
+
+        for node in self.array("nodes") {
+            children.extend(indices(node.get("children"))?);
+        }
+
+        Ok((0..self.array("nodes").len())
+            .filter(|index| !children.contains(index))
+            .collect())
+    }
+}
+
+/// A scene loaded from a glTF 2.0 file, flattened into mesh primitives with world transforms.
+#[derive(Clone, Debug, Default)]
+pub struct GltfScene {
+    /// The mesh primitives of the scene, in node order.
+    pub meshes: Vec<GltfMesh>,
+}
+
+// glTF scene implementation
+impl GltfScene {
+    /// Load a glTF file, which may be a `.gltf` JSON document or a `.glb` binary file. Buffers may be embedded as base64 data URIs, stored in the binary chunk of a `.glb` file, or stored in files relative to the glTF file.
+    ///
+    /// `path`: the path of the glTF file.
+    ///
+    /// Returns a result containing the scene, or the error variant if a file could not be read or is invalid.
+    pub fn load<P: AsRef<Path>>(path: P) -> GltfResult<Self> {
+        let path = path.as_ref();
+
+        Self::from_bytes(&fs::read(path)?, path.parent())
+    }
+
+    /// Read a glTF file, which may be a JSON document or a binary file. Buffers must be embedded, as external files cannot be resolved.
+    ///
+    /// `reader`: the reader from which the file will be read.
+    ///
+    /// Returns a result containing the scene, or the error variant if the file could not be read or is invalid.
+    pub fn read<R: Read>(mut reader: R) -> GltfResult<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        Self::from_bytes(&data, None)
+    }
+
+    /// Parse the contents of a glTF file.
+    ///
+    /// `data`: the contents of the file.
+    /// `directory`: the directory used to resolve external buffer files, if any.
+    ///
+    /// Returns a result containing the scene.
+    fn from_bytes(data: &[u8], directory: Option<&Path>) -> GltfResult<Self> {
+        let (json, binary) = if read_u32(data, 0) == Some(GLB_MAGIC) {
+            Self::split_binary(data)?
+        } else {
+            (data, None)
+        };
+
+        let json = parse_json(json)?;
+        let mut document = Document {
+            json,
+            buffers: Vec::new(),
+        };
+
+        let mut buffers = Vec::new();
+
+        for (index, buffer) in document.array("buffers").iter().enumerate() {
+            let contents = match buffer.get("uri").and_then(|uri| uri.as_str()) {
+                Some(uri) if uri.starts_with("data:") => {
+                    let (header, payload) = uri
+                        .split_once(',')
+                        .ok_or_else(|| invalid("malformed data URI"))?;
+
+                    if !header.ends_with(";base64") {
+                        return Err(invalid("data URIs must be base64 encoded"));
+                    }
+
+                    decode_base64(payload).ok_or_else(|| invalid("invalid base64 data"))?
+                }
+                Some(uri) => match directory {
+                    Some(directory) => fs::read(directory.join(uri))?,
+                    None => {
+                        return Err(invalid(format!(
+                            "external buffer '{}' cannot be resolved",
+                            uri
+                        )))
+                    }
+                },
+                None if index == 0 => binary
+                    .ok_or_else(|| invalid("buffer 0 has no URI and there is no binary chunk"))?
+                    .to_vec(),
+                None => return Err(invalid(format!("buffer {} has no URI", index))),
+            };
+
+            let length = buffer
+                .get("byteLength")
+                .and_then(|length| length.as_usize())
+                .unwrap_or(0);
+
+            if contents.len() < length {
+                return Err(invalid(format!(
+                    "buffer {} is shorter than its byte length",
+                    index
+                )));
+            }
+
+            buffers.push(contents);
+        }
+
+        document.buffers = buffers;
+
+        let mut meshes = Vec::new();
+        let mut visited = vec![false; document.array("nodes").len()];
+
+        for root in document.roots()? {
+            document.visit(root, Matrix::identity(), &mut visited, &mut meshes)?;
+        }
+
+        Ok(Self { meshes })
+    }
+
+    /// Split a binary glTF file into its JSON and binary chunks.
+    ///
+    /// `data`: the contents of the file.
+    ///
+    /// Returns a result containing the JSON chunk and the binary chunk, if any.
+    fn split_binary(data: &[u8]) -> GltfResult<(&[u8], Option<&[u8]>)> {
+        if read_u32(data, 4) != Some(2) {
+            return Err(invalid("unsupported binary glTF version"));
+        }
+
+        let length = read_u32(data, 8).unwrap_or(0) as usize;
+        let data = data
+            .get(..length)
+            .ok_or_else(|| invalid("binary glTF file is shorter than its declared length"))?;
+
+        let mut chunks = Vec::new();
+        let mut offset = 12;
+
+        while offset < data.len() {
+            let chunk_length = read_u32(data, offset)
+                .ok_or_else(|| invalid("truncated binary glTF chunk"))?
+                as usize;
+            let chunk_type =
+                read_u32(data, offset + 4).ok_or_else(|| invalid("truncated binary glTF chunk"))?;
+            let contents = data
+                .get(offset + 8..offset + 8 + chunk_length)
+                .ok_or_else(|| invalid("truncated binary glTF chunk"))?;
+
+            chunks.push((chunk_type, contents));
+            offset += 8 + chunk_length;
+        }
+
+        match chunks.as_slice() {
+            [(GLB_JSON_CHUNK, json), rest @ ..] => Ok((
+                json,
+                rest.iter()
+                    .find(|(kind, _)| *kind == GLB_BIN_CHUNK)
+                    .map(|(_, binary)| *binary),
+            )),
+            _ => Err(invalid("binary glTF file does not start with a JSON chunk")),
+        }
+    }
+
+    /// Combine all meshes of the scene into a single mesh in world space.
+    ///
+    /// Returns the combined mesh.
+    pub fn mesh(&self) -> Mesh<3, f32> {
+        self.meshes
+            .iter()
+            .flat_map(|mesh| mesh.world_mesh().triangles().to_vec())
+            .collect()
+    }
+}
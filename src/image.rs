@@ -0,0 +1,269 @@
+use crate::color::*;
+use crate::screen::*;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// The PNG file signature.
+const PNG_SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+/// The maximum number of bytes in a single stored deflate block.
+const DEFLATE_STORED_BLOCK_SIZE: usize = 65535;
+
+/// Compute the CRC-32 checksum of a sequence of byte slices, as used by PNG chunks.
+///
+/// `parts`: the byte slices to checksum, in order.
+///
+/// Returns the checksum.
+fn crc32(parts: &[&[u8]]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+
+    for part in parts {
+        for &byte in *part {
+            crc ^= byte as u32;
+
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 {
+                    0xEDB8_8320 ^ (crc >> 1)
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+    }
+
+    !crc
+}
+
+/// Compute the Adler-32 checksum of some data, as used by zlib streams.
+///
+/// `data`: the data to checksum.
+///
+/// Returns the checksum.
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+
+        a %= 65521;
+        b %= 65521;
+    }
+
+    (b << 16) | a
+}
+
+/// Wrap data in a zlib stream made up of uncompressed (stored) deflate blocks.
+///
+/// `data`: the data to wrap.
+///
+/// Returns the zlib stream.
+fn zlib_store(data: &[u8]) -> Vec<u8> {
+    let block_count = data.len().div_ceil(DEFLATE_STORED_BLOCK_SIZE).max(1);
+    let mut stream = Vec::with_capacity(data.len() + block_count * 5 + 6);
+
+    // Deflate with a 32K window, no preset dictionary, fastest compression
+    stream.extend_from_slice(&[0x78, 0x01]);
+
+    for i in 0..block_count {
+        let start = i * DEFLATE_STORED_BLOCK_SIZE;
+        let end = (start + DEFLATE_STORED_BLOCK_SIZE).min(data.len());
+        let len = (end - start) as u16;
+
+        stream.push(if i == block_count - 1 { 1 } else { 0 });
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(&data[start..end]);
+    }
+
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+
+    stream
+}
+
+/// Write a single PNG chunk.
+///
+/// `writer`: the writer to which the chunk will be written.
+/// `kind`: the four byte chunk type.
+/// `data`: the chunk data.
+fn write_png_chunk<W: Write>(writer: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(kind)?;
+    writer.write_all(data)?;
+    writer.write_all(&crc32(&[kind, data]).to_be_bytes())
+}
+
+/// A supported image file format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Binary portable pixmap.
+    Ppm,
+    /// Uncompressed 24-bit Windows bitmap.
+    Bmp,
+    /// Portable network graphics, written without compression.
+    Png,
+}
+
+// Image format implementation
+impl ImageFormat {
+    /// Determine an image format from a file path's extension.
+    ///
+    /// `path`: the file path.
+    ///
+    /// Returns an option containing the image format, or the none variant if the extension is not recognized.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "ppm" => Some(Self::Ppm),
+            "bmp" => Some(Self::Bmp),
+            "png" => Some(Self::Png),
+            _ => None,
+        }
+    }
+}
+
+// Image encoding for screen buffers
+impl Screen {
+    /// Get the pixel at a position as a color.
+    ///
+    /// `x`: the x coordinate of the pixel.
+    /// `y`: the y coordinate of the pixel.
+    ///
+    /// Returns the color of the pixel.
+    fn color_at(&self, x: usize, y: usize) -> Color {
+        Color::from_u8_rgb(self[(x, y)])
+    }
+
+    /// Write the screen buffer as a binary PPM image.
+    ///
+    /// `writer`: the writer to which the image will be written.
+    ///
+    /// Returns a result of the write operation.
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let (width, height) = (self.get_width(), self.get_height());
+        let mut data = Vec::with_capacity(width * height * 3);
+
+        for y in 0..height {
+            for x in 0..width {
+                let color = self.color_at(x, y);
+                data.extend_from_slice(&[color[0], color[1], color[2]]);
+            }
+        }
+
+        write!(writer, "P6\n{} {}\n255\n", width, height)?;
+        writer.write_all(&data)?;
+        writer.flush()
+    }
+
+    /// Write the screen buffer as an uncompressed 24-bit BMP image.
+    ///
+    /// `writer`: the writer to which the image will be written.
+    ///
+    /// Returns a result of the write operation.
+    pub fn write_bmp<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let (width, height) = (self.get_width(), self.get_height());
+        let row_size = (width * 3).div_ceil(4) * 4;
+        let pixel_data_size = row_size * height;
+        let header_size = 14 + 40;
+        let mut data = Vec::with_capacity(header_size + pixel_data_size);
+
+        // File header
+        data.extend_from_slice(b"BM");
+        data.extend_from_slice(&((header_size + pixel_data_size) as u32).to_le_bytes());
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&(header_size as u32).to_le_bytes());
+
+        // Info header
+        data.extend_from_slice(&40u32.to_le_bytes());
+        data.extend_from_slice(&(width as i32).to_le_bytes());
+        data.extend_from_slice(&(height as i32).to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&24u16.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&(pixel_data_size as u32).to_le_bytes());
+        data.extend_from_slice(&2835i32.to_le_bytes());
+        data.extend_from_slice(&2835i32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+
+        // Pixel rows, stored bottom-up in BGR order and padded to four bytes
+        for y in (0..height).rev() {
+            for x in 0..width {
+                let color = self.color_at(x, y);
+                data.extend_from_slice(&[color[2], color[1], color[0]]);
+            }
+
+            data.resize(data.len() + row_size - width * 3, 0);
+        }
+
+        writer.write_all(&data)?;
+        writer.flush()
+    }
+
+    /// Write the screen buffer as an RGB PNG image. The image data is stored without compression.
+    ///
+    /// `writer`: the writer to which the image will be written.
+    ///
+    /// Returns a result of the write operation.
+    pub fn write_png<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let (width, height) = (self.get_width(), self.get_height());
+        let mut header = Vec::with_capacity(13);
+
+        header.extend_from_slice(&(width as u32).to_be_bytes());
+        header.extend_from_slice(&(height as u32).to_be_bytes());
+        // 8-bit depth, truecolor, deflate compression, adaptive filtering, no interlace
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        // Each scanline is prefixed with a filter type of zero (none)
+        let mut scanlines = Vec::with_capacity((width * 3 + 1) * height);
+
+        for y in 0..height {
+            scanlines.push(0);
+
+            for x in 0..width {
+                let color = self.color_at(x, y);
+                scanlines.extend_from_slice(&[color[0], color[1], color[2]]);
+            }
+        }
+
+        writer.write_all(&PNG_SIGNATURE)?;
+        write_png_chunk(&mut writer, b"IHDR", &header)?;
+        write_png_chunk(&mut writer, b"IDAT", &zlib_store(&scanlines))?;
+        write_png_chunk(&mut writer, b"IEND", &[])?;
+        writer.flush()
+    }
+
+    /// Write the screen buffer as an image.
+    ///
+    /// `writer`: the writer to which the image will be written.
+    /// `format`: the image format.
+    ///
+    /// Returns a result of the write operation.
+    pub fn write_image<W: Write>(&self, writer: W, format: ImageFormat) -> io::Result<()> {
+        match format {
+            ImageFormat::Ppm => self.write_ppm(writer),
+            ImageFormat::Bmp => self.write_bmp(writer),
+            ImageFormat::Png => self.write_png(writer),
+        }
+    }
+
+    /// Save the screen buffer to an image file. The format is determined by the file extension, which must be one of `ppm`, `bmp` or `png`.
+    ///
+    /// `path`: the path of the image file.
+    ///
+    /// Returns a result of the save operation.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let format = ImageFormat::from_path(&path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "unrecognized image file extension",
+            )
+        })?;
+
+        self.write_image(BufWriter::new(File::create(path)?), format)
+    }
+}
@@ -0,0 +1,338 @@
+use crate::color::*;
+use crate::mesh::*;
+use crate::triangle::*;
+use crate::vector::*;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+
+/// An error type for building indexed meshes from inconsistent buffers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IndexedMeshError {
+    /// A triangle refers to a vertex that does not exist.
+    IndexOutOfRange {
+        /// The out of range vertex index.
+        index: usize,
+        /// The number of vertices in the mesh.
+        vertices: usize,
+    },
+    /// A per-vertex buffer, such as the normals, texture coordinates or colors, has a different length than the vertex buffer.
+    LengthMismatch {
+        /// The number of values in the buffer.
+        length: usize,
+        /// The number of vertices in the mesh.
+        vertices: usize,
+    },
+}
+
+// Display indexed mesh errors
+impl fmt::Display for IndexedMeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IndexOutOfRange { index, vertices } => write!(
+                f,
+                "vertex index {} out of range, only {} vertices",
+                index, vertices
+            ),
+            Self::LengthMismatch { length, vertices } => {
+                write!(f, "{} values given for {} vertices", length, vertices)
+            }
+        }
+    }
+}
+
+// Mark indexed mesh error as an implementation of the standard error trait
+impl error::Error for IndexedMeshError {}
+
+/// A result type for building indexed meshes.
+pub type IndexedMeshResult<T> = Result<T, IndexedMeshError>;
+
+/// An object mesh made up of a buffer of shared vertices with type `T` `N`-dimensional positions, and a buffer of triangles indexing into it. Each vertex may optionally carry a normal, texture coordinates and a color.
+#[derive(Clone, Debug)]
+pub struct IndexedMesh<const N: usize = 3, T: Clone = VectorPointType> {
+    /// The position of each vertex.
+    vertices: Vec<Vector<N, T>>,
+    /// The vertex indices of each triangle.
+    indices: Vec<[usize; 3]>,
+    /// The normal of each vertex, if the mesh has normals.
+    normals: Option<Vec<Vector<3, T>>>,
+    /// The texture coordinates of each vertex, if the mesh has texture coordinates.
+    uvs: Option<Vec<Vector<2, T>>>,
+    /// The color of each vertex, if the mesh has colors.
+    colors: Option<Vec<Color>>,
+}
+
+// Indexed mesh implementation
+impl<const N: usize, T: Clone> IndexedMesh<N, T> {
+    /// Create a new indexed mesh from vertex and index buffers. Panics if any index is out of range, see `try_new` for a fallible alternative.
+    ///
+    /// `vertices`: the position of each vertex.
+    /// `indices`: the vertex indices of each triangle.
+    ///
+    /// Returns the new indexed mesh.
+    pub fn new(vertices: Vec<Vector<N, T>>, indices: Vec<[usize; 3]>) -> Self {
+        Self::try_new(vertices, indices).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create a new indexed mesh from vertex and index buffers, checking that every index is in range.
+    ///
+    /// `vertices`: the position of each vertex.
+    /// `indices`: the vertex indices of each triangle.
+    ///
+    /// Returns a result containing the new indexed mesh, or the error variant if any index is out of range.
+    pub fn try_new(
+        vertices: Vec<Vector<N, T>>,
+        indices: Vec<[usize; 3]>,
+    ) -> IndexedMeshResult<Self> {
+        if let Some(&index) = indices
+            .iter()
+            .flatten()
+            .find(|&&index| index >= vertices.len())
+        {
+            return Err(IndexedMeshError::IndexOutOfRange {
+                index,
+                vertices: vertices.len(),
+            });
+        }
+
+        Ok(Self {
+            vertices,
+            indices,
+            normals: None,
+            uvs: None,
+            colors: None,
+        })
+    }
+
+    /// Check that a per-vertex buffer has a value for every vertex.
+    ///
+    /// `buffer`: the buffer, or the none variant if it is being removed.
+    ///
+    /// Returns a result, or the error variant if the buffer's length does not match the number of vertices.
+    fn check_length<U>(&self, buffer: Option<&[U]>) -> IndexedMeshResult<()> {
+        match buffer {
+            Some(buffer) if buffer.len() != self.vertices.len() => {
+                Err(IndexedMeshError::LengthMismatch {
+                    length: buffer.len(),
+                    vertices: self.vertices.len(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Get the position of each vertex.
+    ///
+    /// Returns the vertex buffer.
+    pub fn vertices(&self) -> &[Vector<N, T>] {
+        &self.vertices
+    }
+
+    /// Get the vertex indices of each triangle.
+    ///
+    /// Returns the index buffer.
+    pub fn indices(&self) -> &[[usize; 3]] {
+        &self.indices
+    }
+
+    /// Get the normal of each vertex.
+    ///
+    /// Returns an option containing the normals, or the none variant if the mesh has none.
+    pub fn get_normals(&self) -> Option<&[Vector<3, T>]> {
+        self.normals.as_deref()
+    }
+
+    /// Set the normal of each vertex. Panics if the number of normals does not match the number of vertices, see `try_set_normals` for a fallible alternative.
+    ///
+    /// `normals`: the normals, or the none variant to remove them.
+    pub fn set_normals(&mut self, normals: Option<Vec<Vector<3, T>>>) {
+        self.try_set_normals(normals)
+            .unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /// Set the normal of each vertex, checking that there is one for every vertex.
+    ///
+    /// `normals`: the normals, or the none variant to remove them.
+    ///
+    /// Returns a result, or the error variant if the number of normals does not match the number of vertices.
+    pub fn try_set_normals(&mut self, normals: Option<Vec<Vector<3, T>>>) -> IndexedMeshResult<()> {
+        self.check_length(normals.as_deref())?;
+        self.normals = normals;
+
+        Ok(())
+    }
+
+    /// Get the texture coordinates of each vertex.
+    ///
+    /// Returns an option containing the texture coordinates, or the none variant if the mesh has none.
+    pub fn get_uvs(&self) -> Option<&[Vector<2, T>]> {
+        self.uvs.as_deref()
+    }
+
+    /// Set the texture coordinates of each vertex. Panics if the number of texture coordinates does not match the number of vertices, see `try_set_uvs` for a fallible alternative.
+    ///
+    /// `uvs`: the texture coordinates, or the none variant to remove them.
+    pub fn set_uvs(&mut self, uvs: Option<Vec<Vector<2, T>>>) {
+        self.try_set_uvs(uvs).unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /// Set the texture coordinates of each vertex, checking that there is one for every vertex.
+    ///
+    /// `uvs`: the texture coordinates, or the none variant to remove them.
+    ///
+    /// Returns a result, or the error variant if the number of texture coordinates does not match the number of vertices.
+    pub fn try_set_uvs(&mut self, uvs: Option<Vec<Vector<2, T>>>) -> IndexedMeshResult<()> {
+        self.check_length(uvs.as_deref())?;
+        self.uvs = uvs;
+
+        Ok(())
+    }
+
+    /// Get the color of each vertex.
+    ///
+    /// Returns an option containing the colors, or the none variant if the mesh has none.
+    pub fn get_colors(&self) -> Option<&[Color]> {
+        self.colors.as_deref()
+    }
+
+    /// Set the color of each vertex. Panics if the number of colors does not match the number of vertices, see `try_set_colors` for a fallible alternative.
+    ///
+    /// `colors`: the colors, or the none variant to remove them.
+    pub fn set_colors(&mut self, colors: Option<Vec<Color>>) {
+        self.try_set_colors(colors)
+            .unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /// Set the color of each vertex, checking that there is one for every vertex.
+    ///
+    /// `colors`: the colors, or the none variant to remove them.
+    ///
+    /// Returns a result, or the error variant if the number of colors does not match the number of vertices.
+    pub fn try_set_colors(&mut self, colors: Option<Vec<Color>>) -> IndexedMeshResult<()> {
+        self.check_length(colors.as_deref())?;
+        self.colors = colors;
+
+        Ok(())
+    }
+
+    /// Transform the position of each unique vertex, leaving the indices and other channels untouched.
+    ///
+    /// `f`: the closure used to map each vertex position.
+    ///
+    /// Returns the resulting indexed mesh.
+    pub fn transform<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&Vector<N, T>) -> Vector<N, T>,
+    {
+        Self {
+            vertices: self.vertices.iter().map(f).collect(),
+            indices: self.indices.clone(),
+            normals: self.normals.clone(),
+            uvs: self.uvs.clone(),
+            colors: self.colors.clone(),
+        }
+    }
+
+    /// Transform the normal of each unique vertex, if the mesh has normals.
+    ///
+    /// `f`: the closure used to map each normal.
+    ///
+    /// Returns the resulting indexed mesh.
+    pub fn transform_normals<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&Vector<3, T>) -> Vector<3, T>,
+    {
+        Self {
+            vertices: self.vertices.clone(),
+            indices: self.indices.clone(),
+            normals: self
+                .normals
+                .as_ref()
+                .map(|normals| normals.iter().map(f).collect()),
+            uvs: self.uvs.clone(),
+            colors: self.colors.clone(),
+        }
+    }
+
+    /// Get the positions of a triangle's vertices, along with their normals if the mesh has normals.
+    ///
+    /// `index`: the index of the triangle.
+    ///
+    /// Returns an option containing the triangle, or the none variant if the index is out of range.
+    pub fn triangle(&self, index: usize) -> Option<Triangle<N, T>> {
+        let corners = self.indices.get(index)?;
+
+        let mut triangle = Triangle::new(corners.map(|vertex| self.vertices[vertex].clone()), 1.0);
+        triangle.set_normals(
+            self.normals
+                .as_ref()
+                .map(|normals| corners.map(|vertex| normals[vertex].clone())),
+        );
+
+        Some(triangle)
+    }
+
+    /// Expand the indexed mesh into independent triangles, duplicating shared vertices.
+    ///
+    /// Returns the expanded mesh.
+    pub fn to_mesh(&self) -> Mesh<N, T> {
+        (0..self.indices.len())
+            .map(|index| self.triangle(index).unwrap())
+            .collect()
+    }
+}
+
+// Convert a mesh into an indexed mesh, merging vertices with identical positions and normals
+impl<const N: usize> From<&Mesh<N, f32>> for IndexedMesh<N, f32> {
+    fn from(mesh: &Mesh<N, f32>) -> Self {
+        // Normals are only kept when every triangle has them, so that each vertex has one
+        let has_normals = mesh
+            .triangles()
+            .iter()
+            .all(|triangle| triangle.get_normals().is_some());
+
+        let mut vertices = Vec::new();
+        let mut normals = Vec::new();
+        let mut lookup: HashMap<([u32; N], Option<[u32; 3]>), usize> = HashMap::new();
+
+        let indices = mesh
+            .triangles()
+            .iter()
+            .map(|triangle| {
+                let corner_normals = triangle.get_normals().filter(|_| has_normals);
+
+                [0, 1, 2].map(|corner| {
+                    let vector = triangle[corner];
+                    let normal = corner_normals.map(|normals| normals[corner]);
+
+                    // Adding zero turns negative zero positive, so both zeroes share a key
+                    let key = (
+                        vector.coords().map(|value| (value + 0.0).to_bits()),
+                        normal.map(|normal| normal.coords().map(|value| (value + 0.0).to_bits())),
+                    );
+
+                    *lookup.entry(key).or_insert_with(|| {
+                        vertices.push(vector);
+                        normals.extend(normal);
+                        vertices.len() - 1
+                    })
+                })
+            })
+            .collect();
+
+        let mut indexed = Self::new(vertices, indices);
+
+        if has_normals {
+            indexed.set_normals(Some(normals));
+        }
+
+        indexed
+    }
+}
+
+// Convert an indexed mesh into a mesh of independent triangles
+impl<const N: usize, T: Clone> From<&IndexedMesh<N, T>> for Mesh<N, T> {
+    fn from(mesh: &IndexedMesh<N, T>) -> Self {
+        mesh.to_mesh()
+    }
+}
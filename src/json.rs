@@ -0,0 +1,402 @@
+use std::collections::HashMap;
+
+/// The maximum nesting depth of arrays and objects, guarding against stack exhaustion on malicious input.
+const MAX_DEPTH: usize = 256;
+
+/// A parsed JSON value.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum JsonValue {
+    /// The null literal.
+    Null,
+    /// A boolean literal.
+    Bool(bool),
+    /// A number.
+    Number(f64),
+    /// A string.
+    String(String),
+    /// An array of values.
+    Array(Vec<JsonValue>),
+    /// An object of named values.
+    Object(HashMap<String, JsonValue>),
+}
+
+// JSON value implementation
+impl JsonValue {
+    /// Get a member of an object.
+    ///
+    /// `key`: the member name.
+    ///
+    /// Returns an option containing the member, or the none variant if this is not an object or has no such member.
+    pub(crate) fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            Self::Object(members) => members.get(key),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a number.
+    ///
+    /// Returns an option containing the number.
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a non-negative integer.
+    ///
+    /// Returns an option containing the integer, or the none variant if this is not a non-negative whole number.
+    pub(crate) fn as_usize(&self) -> Option<usize> {
+        self.as_f64()
+            .filter(|value| *value >= 0.0 && value.fract() == 0.0)
+            .map(|value| value as usize)
+    }
+
+    /// Get the value as a string.
+    ///
+    /// Returns an option containing the string.
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as an array.
+    ///
+    /// Returns an option containing the array items.
+    pub(crate) fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            Self::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// An error parsing JSON text.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct JsonError {
+    /// The line on which the error occurred, starting from one.
+    pub(crate) line: usize,
+    /// A description of the problem.
+    pub(crate) message: String,
+}
+
+/// A recursive descent JSON parser.
+struct Parser<'a> {
+    /// The text being parsed.
+    text: &'a [u8],
+    /// The offset of the next byte.
+    position: usize,
+    /// The current nesting depth.
+    depth: usize,
+}
+
+// JSON parser implementation
+impl<'a> Parser<'a> {
+    /// Create an error at the current position.
+    ///
+    /// `message`: a description of the problem.
+    ///
+    /// Returns the error.
+    fn error<S: Into<String>>(&self, message: S) -> JsonError {
+        let end = self.position.min(self.text.len());
+
+        JsonError {
+            line: self.text[..end]
+                .iter()
+                .filter(|&&byte| byte == b'\n')
+                .count()
+                + 1,
+            message: message.into(),
+        }
+    }
+
+    /// Skip any whitespace.
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.text.get(self.position) {
+            self.position += 1;
+        }
+    }
+
+    /// Look at the next non-whitespace byte without consuming it.
+    ///
+    /// Returns an option containing the byte, or the none variant at the end of the text.
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.text.get(self.position).copied()
+    }
+
+    /// Consume the next non-whitespace byte, which must match an expected byte.
+    ///
+    /// `expected`: the expected byte.
+    ///
+    /// Returns a result of the match.
+    fn expect(&mut self, expected: u8) -> Result<(), JsonError> {
+        match self.peek() {
+            Some(byte) if byte == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(byte) => Err(self.error(format!(
+                "expected '{}', found '{}'",
+                expected as char, byte as char
+            ))),
+            None => Err(self.error(format!(
+                "expected '{}', found end of text",
+                expected as char
+            ))),
+        }
+    }
+
+    /// Consume a literal keyword.
+    ///
+    /// `keyword`: the keyword.
+    /// `value`: the value the keyword represents.
+    ///
+    /// Returns a result containing the value.
+    fn literal(&mut self, keyword: &str, value: JsonValue) -> Result<JsonValue, JsonError> {
+        if self.text[self.position..].starts_with(keyword.as_bytes()) {
+            self.position += keyword.len();
+            Ok(value)
+        } else {
+            Err(self.error("invalid literal"))
+        }
+    }
+
+    /// Parse any value.
+    ///
+    /// Returns a result containing the value.
+    fn value(&mut self) -> Result<JsonValue, JsonError> {
+        match self.peek() {
+            Some(b'{') => self.nested(Self::object),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'"') => self.string().map(JsonValue::String),
+            Some(b't') => self.literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.literal("false", JsonValue::Bool(false)),
+            Some(b'n') => self.literal("null", JsonValue::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(byte) => Err(self.error(format!("unexpected '{}'", byte as char))),
+            None => Err(self.error("unexpected end of text")),
+        }
+    }
+
+    /// Parse a nested array or object, enforcing the maximum nesting depth.
+    ///
+    /// `parse`: the function parsing the nested value.
+    ///
+    /// Returns a result containing the value.
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<JsonValue, JsonError>,
+    ) -> Result<JsonValue, JsonError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+
+        value
+    }
+
+    /// Parse an object.
+    ///
+    /// Returns a result containing the object.
+    fn object(&mut self) -> Result<JsonValue, JsonError> {
+        let mut members = HashMap::new();
+        self.expect(b'{')?;
+
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(JsonValue::Object(members));
+        }
+
+        loop {
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected member name"));
+            }
+
+            let key = self.string()?;
+            self.expect(b':')?;
+            members.insert(key, self.value()?);
+
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b'}') => {
+                    self.position += 1;
+                    return Ok(JsonValue::Object(members));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    /// Parse an array.
+    ///
+    /// Returns a result containing the array.
+    fn array(&mut self) -> Result<JsonValue, JsonError> {
+        let mut items = Vec::new();
+        self.expect(b'[')?;
+
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(JsonValue::Array(items));
+        }
+
+        loop {
+            items.push(self.value()?);
+
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    /// Parse four hexadecimal digits of a unicode escape.
+    ///
+    /// Returns a result containing the code unit.
+    fn hex_escape(&mut self) -> Result<u32, JsonError> {
+        let digits = self
+            .text
+            .get(self.position..self.position + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+
+        self.position += 4;
+
+        Ok(digits)
+    }
+
+    /// Parse a string.
+    ///
+    /// Returns a result containing the unescaped string.
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+
+        loop {
+            let byte = *self
+                .text
+                .get(self.position)
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.position += 1;
+
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escape = *self
+                        .text
+                        .get(self.position)
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.position += 1;
+
+                    let character = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut code = self.hex_escape()?;
+
+                            // Characters outside the basic multilingual plane are escaped as surrogate pairs
+                            if (0xd800..0xdc00).contains(&code)
+                                && self.text[self.position..].starts_with(b"\\u")
+                            {
+                                self.position += 2;
+                                let low = self.hex_escape()?;
+
+                                if !(0xdc00..0xe000).contains(&low) {
+                                    return Err(self.error("invalid unicode escape"));
+                                }
+
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                            }
+
+                            char::from_u32(code)
+                                .ok_or_else(|| self.error("invalid unicode escape"))?
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    };
+
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+                }
+                0..=0x1f => return Err(self.error("control character in string")),
+                _ => bytes.push(byte),
+            }
+        }
+
+        String::from_utf8(bytes).map_err(|_| self.error("invalid text encoding in string"))
+    }
+
+    /// Parse a number.
+    ///
+    /// Returns a result containing the number.
+    fn number(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.position;
+
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') =
+            self.text.get(self.position)
+        {
+            self.position += 1;
+        }
+
+        std::str::from_utf8(&self.text[start..self.position])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .map(JsonValue::Number)
+            .ok_or_else(|| self.error("invalid number"))
+    }
+}
+
+/// Parse JSON text.
+///
+/// `text`: the text to parse.
+///
+/// Returns a result containing the parsed value, or the error variant if the text is not valid JSON.
+pub(crate) fn parse_json(text: &[u8]) -> Result<JsonValue, JsonError> {
+    let mut parser = Parser {
+        text,
+        position: 0,
+        depth: 0,
+    };
+
+    let value = parser.value()?;
+
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected text after value"));
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surrogate_pair_escape() {
+        let value = parse_json(br#""\ud83d\ude00""#).unwrap();
+
+        assert_eq!(value.as_str(), Some("\u{1f600}"));
+    }
+
+    #[test]
+    fn unpaired_high_surrogate_escape() {
+        assert!(parse_json(br#""\ud800\u0041""#).is_err());
+        assert!(parse_json(br#""\ud800\ud800""#).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+// Import all local modules
+mod camera;
+mod clip;
+mod color;
+mod depth_buffer;
+mod game_window;
+mod gltf;
+mod image;
+mod indexed_mesh;
+mod json;
+mod lighting;
+mod matrix;
+mod mesh;
+mod normals;
+mod obj;
+mod ply;
+mod primitives;
+mod quaternion;
+mod renderer;
+mod screen;
+mod stl;
+mod transform;
+mod triangle;
+mod vector;
+
+// Make all module exports visible
+pub use camera::*;
+pub use color::*;
+pub use depth_buffer::*;
+pub use game_window::*;
+pub use gltf::*;
+pub use image::*;
+pub use indexed_mesh::*;
+pub use lighting::*;
+pub use matrix::*;
+pub use mesh::*;
+pub use obj::*;
+pub use ply::*;
+pub use quaternion::*;
+pub use renderer::*;
+pub use screen::*;
+pub use stl::*;
+pub use transform::*;
+pub use triangle::*;
+pub use vector::*;
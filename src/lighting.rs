@@ -0,0 +1,340 @@
+use crate::color::*;
+use crate::vector::*;
+
+/// The reflection model used to calculate specular highlights.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReflectionModel {
+    /// The Phong model, comparing the direction of reflected light with the direction towards the viewer.
+    Phong,
+    /// The Blinn-Phong model, comparing the surface normal with the halfway vector between the directions towards the light and the viewer. Highlights are broader than with the Phong model for the same shininess.
+    #[default]
+    BlinnPhong,
+}
+
+/// The way a surface reflects light, made up of ambient, diffuse and specular terms.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhongMaterial {
+    /// The amount of the surface color shown regardless of lighting.
+    pub ambient: f32,
+    /// The amount of the surface color shown when facing the light.
+    pub diffuse: f32,
+    /// The brightness of specular highlights.
+    pub specular: f32,
+    /// The specular exponent. Higher values give smaller, sharper highlights.
+    pub shininess: f32,
+}
+
+// Support default values for materials
+impl Default for PhongMaterial {
+    fn default() -> Self {
+        Self {
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.5,
+            shininess: 32.0,
+        }
+    }
+}
+
+// Phong material implementation
+impl PhongMaterial {
+    /// Calculate the strength of the specular highlight from a light.
+    ///
+    /// `normal`: the normalized surface normal.
+    /// `light`: the normalized direction from the surface towards the light.
+    /// `view`: the normalized direction from the surface towards the viewer.
+    /// `model`: the reflection model used for specular highlights.
+    ///
+    /// Returns the strength of the highlight, from zero to the material's specular brightness.
+    fn highlight(
+        &self,
+        normal: &Vector<3>,
+        light: &Vector<3>,
+        view: &Vector<3>,
+        model: ReflectionModel,
+    ) -> f32 {
+        let alignment = match model {
+            ReflectionModel::Phong => (-*light).reflect(normal).dot(view),
+            ReflectionModel::BlinnPhong => normal.dot(&(*light + *view).normalize()),
+        };
+
+        alignment.max(0.0).powf(self.shininess) * self.specular
+    }
+
+    /// Light a point on a surface. Specular highlights take the color of the light, which is white.
+    ///
+    /// `color`: the color of the surface.
+    /// `normal`: the normalized surface normal.
+    /// `light`: the normalized direction from the surface towards the light.
+    /// `view`: the normalized direction from the surface towards the viewer.
+    /// `model`: the reflection model used for specular highlights.
+    ///
+    /// Returns the lit color.
+    pub fn shade(
+        &self,
+        color: Color,
+        normal: &Vector<3>,
+        light: &Vector<3>,
+        view: &Vector<3>,
+        model: ReflectionModel,
+    ) -> Color {
+        let diffuse = normal.dot(light).max(0.0);
+
+        // Surfaces facing away from the light have no highlight
+        let specular = if diffuse > 0.0 {
+            self.highlight(normal, light, view, model) * 255.0
+        } else {
+            0.0
+        };
+
+        let lum = self.ambient + self.diffuse * diffuse;
+        let channel = |value: u8| (value as f32 * lum + specular).round().clamp(0.0, 255.0) as u8;
+
+        Color::new(channel(color[0]), channel(color[1]), channel(color[2]))
+    }
+
+    /// Light a point on a surface with colored light, adding up the light received from each source. Diffuse light and specular highlights are tinted by the color of each light, and ambient lights add their color evenly on top of the material's ambient term.
+    ///
+    /// `color`: the color of the surface.
+    /// `position`: the position of the point, in the same space as the lights.
+    /// `normal`: the normalized surface normal.
+    /// `view`: the normalized direction from the surface towards the viewer.
+    /// `lights`: the light sources.
+    /// `model`: the reflection model used for specular highlights.
+    ///
+    /// Returns the lit color.
+    pub fn shade_lights(
+        &self,
+        color: Color,
+        position: &Vector<3>,
+        normal: &Vector<3>,
+        view: &Vector<3>,
+        lights: &[Light],
+        model: ReflectionModel,
+    ) -> Color {
+        let mut diffuse = Vector::from([self.ambient; 3]);
+        let mut specular = Vector::from([0.0; 3]);
+
+        for light in lights {
+            match light.incidence(position) {
+                Some((towards, amount)) => {
+                    let facing = normal.dot(&towards);
+
+                    // Surfaces facing away from the light receive neither diffuse light nor a highlight
+                    if facing > 0.0 {
+                        let radiance = light.radiance() * amount;
+                        diffuse += radiance * (self.diffuse * facing);
+                        specular += radiance * self.highlight(normal, &towards, view, model);
+                    }
+                }
+                None => diffuse += light.radiance(),
+            }
+        }
+
+        let channel = |i: usize| {
+            (color[i] as f32 * diffuse[i] + specular[i] * 255.0)
+                .round()
+                .clamp(0.0, 255.0) as u8
+        };
+
+        Color::new(channel(0), channel(1), channel(2))
+    }
+}
+
+/// The falloff of a light's intensity with distance, dividing it by `constant + linear * distance + quadratic * distance^2`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Attenuation {
+    /// The constant factor.
+    pub constant: f32,
+    /// The factor proportional to distance.
+    pub linear: f32,
+    /// The factor proportional to the square of distance.
+    pub quadratic: f32,
+}
+
+// Support default values for attenuation
+impl Default for Attenuation {
+    fn default() -> Self {
+        Self {
+            constant: 1.0,
+            linear: 0.0,
+            quadratic: 1.0,
+        }
+    }
+}
+
+// Attenuation implementation
+impl Attenuation {
+    /// Calculate how much of a light's intensity remains at a distance.
+    ///
+    /// `distance`: the distance from the light.
+    ///
+    /// Returns the factor by which to scale the light's intensity.
+    pub fn factor(&self, distance: f32) -> f32 {
+        let divisor = self.constant + self.linear * distance + self.quadratic * distance * distance;
+
+        if divisor > 0.0 {
+            1.0 / divisor
+        } else {
+            1.0
+        }
+    }
+}
+
+/// A source of colored light. Positions and directions are in the same space as the mesh being lit, which is usually view space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Light {
+    /// Light reaching every surface equally, regardless of its orientation.
+    Ambient {
+        /// The color of the light.
+        color: Color,
+        /// The brightness of the light.
+        intensity: f32,
+    },
+    /// Light arriving from a single direction, such as sunlight.
+    Directional {
+        /// The direction in which the light travels.
+        direction: Vector<3>,
+        /// The color of the light.
+        color: Color,
+        /// The brightness of the light.
+        intensity: f32,
+    },
+    /// Light shining in every direction from a point, fading with distance.
+    Point {
+        /// The position of the light.
+        position: Vector<3>,
+        /// The color of the light.
+        color: Color,
+        /// The brightness of the light.
+        intensity: f32,
+        /// The falloff of the light with distance.
+        attenuation: Attenuation,
+    },
+    /// Light shining from a point within a cone, fading with distance and towards the edge of the cone.
+    Spot {
+        /// The position of the light.
+        position: Vector<3>,
+        /// The direction in which the cone points.
+        direction: Vector<3>,
+        /// The angle in radians between the cone's axis and the edge of its fully lit center.
+        inner_angle: f32,
+        /// The angle in radians between the cone's axis and its edge, beyond which no light reaches.
+        outer_angle: f32,
+        /// The color of the light.
+        color: Color,
+        /// The brightness of the light.
+        intensity: f32,
+        /// The falloff of the light with distance.
+        attenuation: Attenuation,
+    },
+}
+
+// Light implementation
+impl Light {
+    /// Get the color and brightness of the light.
+    ///
+    /// Returns the red, green and blue light emitted, where one leaves a color unchanged.
+    pub fn radiance(&self) -> Vector<3> {
+        let (color, intensity) = match *self {
+            Self::Ambient { color, intensity }
+            | Self::Directional {
+                color, intensity, ..
+            }
+            | Self::Point {
+                color, intensity, ..
+            }
+            | Self::Spot {
+                color, intensity, ..
+            } => (color, intensity),
+        };
+
+        Vector::from([color[0], color[1], color[2]].map(|value| value as f32 / 255.0)) * intensity
+    }
+
+    /// Calculate the direction from which the light arrives at a point, and how much of it reaches the point.
+    ///
+    /// `position`: the position of the point.
+    ///
+    /// Returns an option containing the normalized direction from the point towards the light and the fraction of the light's intensity reaching the point, or the none variant if the light reaches the point from no particular direction, as with ambient light or a point lying exactly on the light.
+    pub fn incidence(&self, position: &Vector<3>) -> Option<(Vector<3>, f32)> {
+        match self {
+            Self::Ambient { .. } => None,
+            Self::Directional { direction, .. } => Some((-direction.normalize(), 1.0)),
+            Self::Point {
+                position: source,
+                attenuation,
+                ..
+            } => {
+                let offset = *source - *position;
+                let distance = offset.length();
+
+                // A point lying exactly on the light has no direction towards it
+                if distance == 0.0 {
+                    return None;
+                }
+
+                Some((offset / distance, attenuation.factor(distance)))
+            }
+            Self::Spot {
+                position: source,
+                direction,
+                inner_angle,
+                outer_angle,
+                attenuation,
+                ..
+            } => {
+                let offset = *source - *position;
+                let distance = offset.length();
+
+                if distance == 0.0 {
+                    return None;
+                }
+
+                // Fade linearly from the edge of the inner cone to the edge of the outer cone
+                let towards = offset / distance;
+                let alignment = -towards.dot(&direction.normalize());
+                let (inner_cos, outer_cos) = (inner_angle.cos(), outer_angle.cos());
+                let cone = if inner_cos > outer_cos {
+                    ((alignment - outer_cos) / (inner_cos - outer_cos)).clamp(0.0, 1.0)
+                } else if alignment >= outer_cos {
+                    1.0
+                } else {
+                    0.0
+                };
+
+                Some((towards, attenuation.factor(distance) * cone))
+            }
+        }
+    }
+
+    /// Calculate the diffuse light received by a point on a surface.
+    ///
+    /// `position`: the position of the point.
+    /// `normal`: the normalized surface normal at the point.
+    ///
+    /// Returns the red, green and blue light received, where one leaves a color unchanged.
+    pub fn illuminate(&self, position: &Vector<3>, normal: &Vector<3>) -> Vector<3> {
+        match self.incidence(position) {
+            Some((towards, amount)) => self.radiance() * (normal.dot(&towards).max(0.0) * amount),
+            None => self.radiance(),
+        }
+    }
+}
+
+/// The way the renderer lights the triangles it fills.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Shading {
+    /// Use the colored light at each triangle's vectors, as calculated by `Transform::apply_lights`, or otherwise the luminance at each triangle's vectors, as calculated by `Transform::apply_luminance` and scaled by the shadow intensity. Either is interpolated across each triangle.
+    #[default]
+    Luminance,
+    /// Light every pixel from its interpolated view space position and normal. Triangles without normals are lit by their face normal. Pixels are lit by this mode's own lights, so any light or luminance calculated by `Transform::apply_lights` or `Transform::apply_luminance` is not used.
+    PerPixel {
+        /// The light sources, in view space.
+        lights: Vec<Light>,
+        /// The surface's response to light.
+        material: PhongMaterial,
+        /// The reflection model used for specular highlights.
+        model: ReflectionModel,
+    },
+}
@@ -1,5 +1,5 @@
 use crate::vector::*;
-use std::ops::{Index, IndexMut, Mul};
+use std::ops::{Add, Index, IndexMut, Mul};
 
 /// The default type for a matrix element.
 pub type MatrixElementType = f32;
@@ -60,12 +60,54 @@ impl<const M: usize, const N: usize, T: Default + Copy> Matrix<M, N, T> {
     }
 }
 
+// Matrix initialization from rows
+impl<const M: usize, const N: usize, T: Copy + Default> Matrix<M, N, T> {
+    /// Create a matrix from its rows.
+    ///
+    /// `rows`: the `M` rows of the matrix, each containing `N` elements.
+    ///
+    /// Returns the new matrix.
+    pub fn from_rows(rows: [[T; N]; M]) -> Self {
+        let mut matrix = Self::new();
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &value) in row.iter().enumerate() {
+                matrix[(x, y)] = value;
+            }
+        }
+
+        matrix
+    }
+
+    /// Transpose the matrix, swapping its rows and columns.
+    ///
+    /// Returns the transposed matrix.
+    pub fn transpose(&self) -> Matrix<N, M, T> {
+        let mut transposed = Matrix::<N, M, T>::new();
+
+        for x in 0..N {
+            for y in 0..M {
+                transposed[(y, x)] = self[(x, y)];
+            }
+        }
+
+        transposed
+    }
+}
+
+// Default matrices
+impl<const M: usize, const N: usize, T: Default + Copy> Default for Matrix<M, N, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // Indexing operations for matrices
 impl<const M: usize, const N: usize, T> Index<(usize, usize)> for Matrix<M, N, T> {
     type Output = T;
 
     fn index(&self, index: (usize, usize)) -> &Self::Output {
-        &self.get(index.0, index.1).unwrap()
+        self.get(index.0, index.1).unwrap()
     }
 }
 
@@ -76,29 +118,57 @@ impl<const M: usize, const N: usize, T> IndexMut<(usize, usize)> for Matrix<M, N
     }
 }
 
-// Implementation for multiplying a matrix with a vector, primarily to be used for projection matrices
+// Implementation for multiplying a matrix with a vector of matching dimension, without any perspective divide
+impl<const M: usize, const N: usize, T> Mul<Vector<N, T>> for Matrix<M, N, T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Vector<M, T>;
+
+    fn mul(self, rhs: Vector<N, T>) -> Self::Output {
+        let mut out_vec = Vector::from([T::default(); M]);
+
+        for y in 0..M {
+            out_vec[y] = (0..N).fold(T::default(), |sum, x| sum + self[(x, y)] * rhs[x]);
+        }
+
+        out_vec
+    }
+}
+
+// Implementation for multiplying a matrix with a 3D point, primarily to be used for affine and projection matrices. The point is treated as having a w component of one, and the result is divided by the resulting w component when it is non-zero.
 impl Mul<Vector<3>> for Matrix<4, 4, f32> {
     type Output = Vector<3>;
 
     fn mul(self, rhs: Vector<3>) -> Self::Output {
-        let x =
-            rhs[0] * self[(0, 0)] + rhs[1] * self[(1, 0)] + rhs[2] * self[(2, 0)] + self[(3, 0)];
-        let y =
-            rhs[0] * self[(0, 1)] + rhs[1] * self[(1, 1)] + rhs[2] * self[(2, 1)] + self[(3, 1)];
-        let z =
-            rhs[0] * self[(0, 2)] + rhs[1] * self[(1, 2)] + rhs[2] * self[(2, 2)] + self[(3, 2)];
-        let w =
-            rhs[0] * self[(0, 3)] + rhs[1] * self[(1, 3)] + rhs[2] * self[(2, 3)] + self[(3, 3)];
-
-        let mut out_vec = Vector::from([x, y, z]);
-
-        if w != 0.0 {
-            out_vec[0] /= w;
-            out_vec[1] /= w;
-            out_vec[2] /= w;
+        let out_vec = self * rhs.extend(1.0);
+
+        if out_vec[3] != 0.0 {
+            out_vec.perspective_divide()
+        } else {
+            out_vec.truncate()
         }
+    }
+}
 
-        out_vec
+// Implementation for multiplying two matrices, where the right-hand matrix is applied first when transforming vectors
+impl<const M: usize, const N: usize, const P: usize, T> Mul<Matrix<N, P, T>> for Matrix<M, N, T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Matrix<M, P, T>;
+
+    fn mul(self, rhs: Matrix<N, P, T>) -> Self::Output {
+        let mut product = Matrix::<M, P, T>::new();
+
+        for x in 0..P {
+            for y in 0..M {
+                product[(x, y)] =
+                    (0..N).fold(T::default(), |sum, k| sum + self[(k, y)] * rhs[(x, k)]);
+            }
+        }
+
+        product
     }
 }
 
@@ -116,4 +186,120 @@ impl<const N: usize> Matrix<N, N, f32> {
 
         matrix
     }
+
+    /// Compute the determinant of the matrix using Gaussian elimination with partial pivoting.
+    ///
+    /// Returns the determinant.
+    pub fn determinant(&self) -> f32 {
+        let mut matrix = *self;
+        let mut determinant = 1.0;
+
+        for col in 0..N {
+            // Choose the row with the largest value in this column as the pivot
+            let pivot_row = (col..N)
+                .max_by(|&a, &b| matrix[(col, a)].abs().total_cmp(&matrix[(col, b)].abs()))
+                .unwrap();
+            let pivot = matrix[(col, pivot_row)];
+
+            if pivot == 0.0 {
+                return 0.0;
+            }
+
+            if pivot_row != col {
+                matrix.swap_rows(col, pivot_row);
+                determinant = -determinant;
+            }
+
+            determinant *= pivot;
+
+            for row in (col + 1)..N {
+                let factor = matrix[(col, row)] / pivot;
+
+                for x in col..N {
+                    matrix[(x, row)] -= factor * matrix[(x, col)];
+                }
+            }
+        }
+
+        determinant
+    }
+
+    /// Compute the inverse of the matrix using Gauss-Jordan elimination with scaled partial pivoting. Each pivot is compared against the largest element of its own row, so matrices whose rows differ greatly in scale, such as diagonal matrices with widely varying elements, are still inverted.
+    ///
+    /// Returns an option containing the inverse matrix, or the none variant if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let mut matrix = *self;
+        let mut inverse = Self::identity();
+
+        // Pivots this small relative to the elements of their row are treated as zero
+        let mut scales: [f32; N] =
+            std::array::from_fn(|y| (0..N).map(|x| self[(x, y)].abs()).fold(0.0, f32::max));
+        let tolerance = (N as f32) * f32::EPSILON;
+
+        for col in 0..N {
+            let scaled = |row: usize| matrix[(col, row)].abs() / scales[row];
+            let pivot_row = (col..N)
+                .max_by(|&a, &b| scaled(a).total_cmp(&scaled(b)))
+                .unwrap();
+            let pivot = matrix[(col, pivot_row)];
+
+            if pivot.abs() <= scales[pivot_row] * tolerance || !pivot.is_finite() {
+                return None;
+            }
+
+            matrix.swap_rows(col, pivot_row);
+            inverse.swap_rows(col, pivot_row);
+            scales.swap(col, pivot_row);
+
+            for x in 0..N {
+                matrix[(x, col)] /= pivot;
+                inverse[(x, col)] /= pivot;
+            }
+
+            for row in (0..N).filter(|&row| row != col) {
+                let factor = matrix[(col, row)];
+
+                for x in 0..N {
+                    matrix[(x, row)] -= factor * matrix[(x, col)];
+                    inverse[(x, row)] -= factor * inverse[(x, col)];
+                }
+            }
+        }
+
+        Some(inverse)
+    }
+
+    /// Swap two rows of the matrix.
+    ///
+    /// `a`: the index of one row.
+    /// `b`: the index of the other row.
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for x in 0..N {
+            self.matrix[x].swap(a, b);
+        }
+    }
+}
+
+// 3x3 matrix implementation
+impl Matrix<3, 3, f32> {
+    /// Compute the matrix transforming surface normals, so that they stay perpendicular to a surface transformed by this matrix. This is the cofactor matrix, which equals the inverse transpose scaled by the determinant, with its sign chosen so that mirroring transformations do not flip normals. Unlike the inverse transpose, it is defined for every matrix, including singular ones, so transformed normals should be normalized afterwards.
+    ///
+    /// Returns the normal matrix.
+    pub fn normal_matrix(&self) -> Self {
+        let column = |x: usize| Vector::from([self[(x, 0)], self[(x, 1)], self[(x, 2)]]);
+        let [a, b, c] = [0, 1, 2].map(column);
+
+        // Each column of the cofactor matrix is the cross product of the other two columns
+        let sign = if a.dot(&b.cross(&c)) < 0.0 { -1.0 } else { 1.0 };
+        let columns = [b.cross(&c), c.cross(&a), a.cross(&b)];
+        let mut normal_matrix = Self::new();
+
+        for (x, column) in columns.iter().enumerate() {
+            for y in 0..3 {
+                normal_matrix[(x, y)] = column[y] * sign;
+            }
+        }
+
+        normal_matrix
+    }
 }
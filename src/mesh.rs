@@ -1,5 +1,7 @@
 use crate::triangle::*;
 use crate::vector::*;
+use std::fmt;
+use std::ops::{Add, Mul};
 
 /// An object mesh comprised of triangles with type `T` `N`-dimensional vectors.
 #[derive(Clone, Debug)]
@@ -26,6 +28,13 @@ impl<const N: usize, T: Clone> Mesh<N, T> {
         self.tris.as_slice()
     }
 
+    /// Add a triangle to the mesh.
+    ///
+    /// `triangle`: the triangle to add.
+    pub fn push(&mut self, triangle: Triangle<N, T>) {
+        self.tris.push(triangle);
+    }
+
     /// Transform each triangle in the mesh.
     ///
     /// `f`: the closure used to map each triangle.
@@ -41,6 +50,74 @@ impl<const N: usize, T: Clone> Mesh<N, T> {
     }
 }
 
+// Mesh translation
+impl<const N: usize, T: Clone + fmt::Debug + Add<Output = T>> Mesh<N, T> {
+    /// Translate every vector in the mesh.
+    ///
+    /// `values`: the amount by which to translate the mesh in each dimension.
+    ///
+    /// Returns the translated mesh.
+    pub fn translate(&self, values: [T; N]) -> Self {
+        self.transform(|triangle| {
+            triangle.transform(|vector| {
+                let mut new_vector = vector.clone();
+
+                for i in 0..N {
+                    new_vector[i] = vector[i].clone() + values[i].clone();
+                }
+
+                new_vector
+            })
+        })
+    }
+}
+
+// Mesh scaling
+impl<const N: usize, T: Clone + fmt::Debug + Mul<Output = T>> Mesh<N, T> {
+    /// Scale every vector in the mesh.
+    ///
+    /// `factors`: the factors by which to scale the mesh in each dimension.
+    ///
+    /// Returns the scaled mesh.
+    pub fn scale(&self, factors: [T; N]) -> Self {
+        self.transform(|triangle| {
+            triangle.transform(|vector| {
+                let mut new_vector = vector.clone();
+
+                for i in 0..N {
+                    new_vector[i] = vector[i].clone() * factors[i].clone();
+                }
+
+                new_vector
+            })
+        })
+    }
+}
+
+// Mesh geometry
+impl<const N: usize> Mesh<N, f32> {
+    /// Calculate the centroid of the mesh, as the average of its triangles' vectors.
+    ///
+    /// Returns the centroid, or the origin if the mesh has no triangles.
+    pub fn centroid(&self) -> Vector<N, f32> {
+        let mut centroid = Vector::from([0.0; N]);
+
+        if self.tris.is_empty() {
+            return centroid;
+        }
+
+        for triangle in &self.tris {
+            for vector in triangle.vectors() {
+                centroid += *vector;
+            }
+        }
+
+        let count = (self.tris.len() * 3) as f32;
+
+        centroid / count
+    }
+}
+
 // Convert a vector of triangles into a mesh
 impl<const N: usize, T: Clone, U: Into<Triangle<N, T>>> From<Vec<U>> for Mesh<N, T> {
     fn from(triangles: Vec<U>) -> Self {
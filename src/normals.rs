@@ -0,0 +1,163 @@
+use crate::indexed_mesh::*;
+use crate::mesh::*;
+use crate::vector::*;
+use std::collections::HashMap;
+
+/// Calculate the interior angle of a triangle at one of its vertices.
+///
+/// `vertices`: the positions of the triangle's vertices.
+/// `corner`: the index of the vertex within the triangle.
+///
+/// Returns the angle in radians.
+fn corner_angle(vertices: &[Vector<3>; 3], corner: usize) -> f32 {
+    let a = vertices[(corner + 1) % 3] - vertices[corner];
+    let b = vertices[(corner + 2) % 3] - vertices[corner];
+
+    a.cross(&b).length().atan2(a.dot(&b))
+}
+
+/// Calculate a smooth normal for every corner of every triangle. Each corner's normal averages the face normals of the triangles sharing its vertex, weighted by their angles at the vertex, so that the result does not depend on how the surface was triangulated. Triangles meeting at more than the crease angle are not averaged together, keeping hard edges sharp.
+///
+/// `vertices`: the position of each vertex.
+/// `indices`: the vertex indices of each triangle.
+/// `crease_angle`: the largest angle in radians between two faces that are smoothed together.
+///
+/// Returns the normal of each corner of each triangle.
+fn corner_normals(
+    vertices: &[Vector<3>],
+    indices: &[[usize; 3]],
+    crease_angle: f32,
+) -> Vec<[Vector<3>; 3]> {
+    let zero = Vector::from([0.0; 3]);
+    let crease_cos = crease_angle.cos();
+
+    // Degenerate triangles have no face normal, and so contribute nothing
+    let faces: Vec<(Vector<3>, [f32; 3])> = indices
+        .iter()
+        .map(|corners| {
+            let positions = corners.map(|vertex| vertices[vertex]);
+            let cross = (positions[1] - positions[0]).cross(&(positions[2] - positions[0]));
+            let normal = if cross.length_squared() > 0.0 {
+                cross.normalize()
+            } else {
+                zero
+            };
+
+            (
+                normal,
+                [0, 1, 2].map(|corner| corner_angle(&positions, corner)),
+            )
+        })
+        .collect();
+
+    let mut incident: Vec<Vec<(usize, usize)>> = vec![Vec::new(); vertices.len()];
+
+    for (face, corners) in indices.iter().enumerate() {
+        for (corner, &vertex) in corners.iter().enumerate() {
+            incident[vertex].push((face, corner));
+        }
+    }
+
+    indices
+        .iter()
+        .enumerate()
+        .map(|(face, corners)| {
+            let face_normal = faces[face].0;
+
+            [0, 1, 2].map(|corner| {
+                let sum = incident[corners[corner]]
+                    .iter()
+                    .filter(|(other, _)| faces[*other].0.dot(&face_normal) >= crease_cos)
+                    .fold(zero, |sum, &(other, other_corner)| {
+                        sum + faces[other].0 * faces[other].1[other_corner]
+                    });
+
+                if sum.length_squared() > 0.0 {
+                    sum.normalize()
+                } else {
+                    face_normal
+                }
+            })
+        })
+        .collect()
+}
+
+// Smooth normal generation for indexed meshes
+impl IndexedMesh<3, f32> {
+    /// Calculate smooth vertex normals from the mesh's faces, averaging the normals of neighbouring triangles weighted by their angles at each vertex. Vertices along creases sharper than the crease angle are split, so that each side of the crease keeps its own normal.
+    ///
+    /// `crease_angle`: the largest angle in radians between two faces that are smoothed together. An angle of pi smooths every face.
+    ///
+    /// Returns the indexed mesh with vertex normals.
+    pub fn smooth_normals(&self, crease_angle: f32) -> Self {
+        let normals = corner_normals(self.vertices(), self.indices(), crease_angle);
+
+        let mut sources = Vec::new();
+        let mut vertex_normals = Vec::new();
+        let mut lookup: HashMap<(usize, [u32; 3]), usize> = HashMap::new();
+
+        let indices = self
+            .indices()
+            .iter()
+            .zip(&normals)
+            .map(|(corners, normals)| {
+                [0, 1, 2].map(|corner| {
+                    let key = (corners[corner], normals[corner].coords().map(f32::to_bits));
+
+                    *lookup.entry(key).or_insert_with(|| {
+                        sources.push(corners[corner]);
+                        vertex_normals.push(normals[corner]);
+                        sources.len() - 1
+                    })
+                })
+            })
+            .collect();
+
+        let mut mesh = IndexedMesh::new(
+            sources
+                .iter()
+                .map(|&vertex| self.vertices()[vertex])
+                .collect(),
+            indices,
+        );
+        mesh.set_normals(Some(vertex_normals));
+        mesh.set_uvs(
+            self.get_uvs()
+                .map(|uvs| sources.iter().map(|&vertex| uvs[vertex]).collect()),
+        );
+        mesh.set_colors(
+            self.get_colors()
+                .map(|colors| sources.iter().map(|&vertex| colors[vertex]).collect()),
+        );
+
+        mesh
+    }
+}
+
+// Smooth normal generation for meshes
+impl Mesh<3, f32> {
+    /// Calculate smooth normals for each triangle's vectors, averaging the normals of neighbouring triangles weighted by their angles at each shared position. Triangles meeting at a crease sharper than the crease angle keep separate normals.
+    ///
+    /// `crease_angle`: the largest angle in radians between two faces that are smoothed together. An angle of pi smooths every face.
+    ///
+    /// Returns the mesh with smooth normals.
+    pub fn smooth_normals(&self, crease_angle: f32) -> Self {
+        // Any existing normals are dropped so that vertices are only merged by position
+        let indexed = IndexedMesh::from(&self.transform(|triangle| {
+            let mut triangle = *triangle;
+            triangle.set_normals(None);
+            triangle
+        }));
+        let normals = corner_normals(indexed.vertices(), indexed.indices(), crease_angle);
+
+        self.triangles()
+            .iter()
+            .zip(normals)
+            .map(|(triangle, normals)| {
+                let mut triangle = *triangle;
+                triangle.set_normals(Some(normals));
+                triangle
+            })
+            .collect()
+    }
+}
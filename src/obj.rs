@@ -0,0 +1,508 @@
+use crate::color::*;
+use crate::mesh::*;
+use crate::triangle::*;
+use crate::vector::*;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::str::{FromStr, SplitWhitespace};
+
+/// An error type for loading Wavefront OBJ and MTL files.
+#[derive(Debug)]
+pub enum ObjError {
+    /// An error reading a file.
+    IOError(io::Error),
+    /// A malformed statement in an OBJ file.
+    ParseError {
+        /// The line number of the statement, starting from one.
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+    /// A malformed statement in an MTL material library referenced by an OBJ file.
+    MaterialParseError {
+        /// The name of the material library.
+        library: String,
+        /// The line number of the statement, starting from one.
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+}
+
+// Convert I/O errors to OBJ errors
+impl From<io::Error> for ObjError {
+    fn from(e: io::Error) -> Self {
+        Self::IOError(e)
+    }
+}
+
+// Display OBJ errors
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IOError(e) => write!(f, "{}", e),
+            Self::ParseError { line, message } => write!(f, "line {}: {}", line, message),
+            Self::MaterialParseError {
+                library,
+                line,
+                message,
+            } => write!(f, "{} line {}: {}", library, line, message),
+        }
+    }
+}
+
+// Mark OBJ error as an implementation of the standard error trait
+impl error::Error for ObjError {}
+
+/// A result type for loading OBJ and MTL files.
+pub type ObjResult<T> = Result<T, ObjError>;
+
+/// Create a parse error.
+///
+/// `line`: the line number of the statement.
+/// `message`: a description of the problem.
+///
+/// Returns the parse error.
+fn parse_error<S: Into<String>>(line: usize, message: S) -> ObjError {
+    ObjError::ParseError {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Parse the next value of a statement.
+///
+/// `values`: the remaining values of the statement.
+/// `line`: the line number of the statement.
+/// `what`: a description of the value, used in error messages.
+///
+/// Returns a result containing the parsed value, or the error variant if it is missing or malformed.
+fn parse_next<T: FromStr>(values: &mut SplitWhitespace, line: usize, what: &str) -> ObjResult<T> {
+    let value = values
+        .next()
+        .ok_or_else(|| parse_error(line, format!("missing {}", what)))?;
+
+    value
+        .parse()
+        .map_err(|_| parse_error(line, format!("invalid {} '{}'", what, value)))
+}
+
+/// Parse the next value of a statement if one is present.
+///
+/// `values`: the remaining values of the statement.
+/// `line`: the line number of the statement.
+/// `what`: a description of the value, used in error messages.
+///
+/// Returns a result containing an option with the parsed value, or the error variant if it is malformed.
+fn parse_optional<T: FromStr>(
+    values: &mut SplitWhitespace,
+    line: usize,
+    what: &str,
+) -> ObjResult<Option<T>> {
+    match values.next() {
+        Some(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| parse_error(line, format!("invalid {} '{}'", what, value))),
+        None => Ok(None),
+    }
+}
+
+/// Parse three floating point values, such as a position or a color.
+///
+/// `values`: the remaining values of the statement.
+/// `line`: the line number of the statement.
+/// `what`: a description of the values, used in error messages.
+///
+/// Returns a result containing the three values.
+fn parse_triple(values: &mut SplitWhitespace, line: usize, what: &str) -> ObjResult<[f32; 3]> {
+    Ok([
+        parse_next(values, line, what)?,
+        parse_next(values, line, what)?,
+        parse_next(values, line, what)?,
+    ])
+}
+
+/// Resolve a possibly negative OBJ index into a zero-based index.
+///
+/// `index`: the index as written in the file, where positive indices start from one and negative indices count back from the most recent element.
+/// `count`: the number of elements defined so far.
+/// `line`: the line number of the statement.
+///
+/// Returns a result containing the zero-based index, or the error variant if the index is out of range.
+fn resolve_index(index: &str, count: usize, line: usize) -> ObjResult<usize> {
+    let value: i64 = index
+        .parse()
+        .map_err(|_| parse_error(line, format!("invalid index '{}'", index)))?;
+
+    let resolved = match value {
+        0 => None,
+        v if v > 0 => Some(v as usize - 1),
+        v => count.checked_sub(v.unsigned_abs() as usize),
+    };
+
+    match resolved {
+        Some(i) if i < count => Ok(i),
+        _ => Err(parse_error(
+            line,
+            format!("index {} out of range, only {} defined", value, count),
+        )),
+    }
+}
+
+/// A material from an MTL material library.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Material {
+    /// The name of the material.
+    pub name: String,
+    /// The ambient color, with each channel from zero to one.
+    pub ambient: [f32; 3],
+    /// The diffuse color, with each channel from zero to one.
+    pub diffuse: [f32; 3],
+    /// The specular color, with each channel from zero to one.
+    pub specular: [f32; 3],
+    /// The specular exponent.
+    pub shininess: f32,
+    /// The opacity, from zero for fully transparent to one for fully opaque.
+    pub opacity: f32,
+    /// The file name of the diffuse texture map, if any.
+    pub diffuse_map: Option<String>,
+}
+
+// Material implementation
+impl Material {
+    /// Create a new material with default properties.
+    ///
+    /// `name`: the name of the material.
+    ///
+    /// Returns the new material.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            ambient: [0.0; 3],
+            diffuse: [1.0; 3],
+            specular: [0.0; 3],
+            shininess: 0.0,
+            opacity: 1.0,
+            diffuse_map: None,
+        }
+    }
+
+    /// Get the diffuse color of the material.
+    ///
+    /// Returns the diffuse color.
+    pub fn color(&self) -> Color {
+        let [r, g, b] = self
+            .diffuse
+            .map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8);
+
+        Color::new(r, g, b)
+    }
+
+    /// Parse an MTL material library.
+    ///
+    /// `reader`: the reader from which the library will be read.
+    ///
+    /// Returns a result containing the materials in the library, or the error variant if the library could not be read or parsed.
+    pub fn parse_library<R: BufRead>(reader: R) -> ObjResult<Vec<Material>> {
+        let mut materials: Vec<Material> = Vec::new();
+
+        for (index, text) in reader.lines().enumerate() {
+            let text = text?;
+            let line = index + 1;
+            let text = text.split('#').next().unwrap_or("");
+            let mut values = text.split_whitespace();
+
+            let keyword = match values.next() {
+                Some(keyword) => keyword,
+                None => continue,
+            };
+
+            if keyword == "newmtl" {
+                let name = values.collect::<Vec<_>>().join(" ");
+
+                if name.is_empty() {
+                    return Err(parse_error(line, "missing material name"));
+                }
+
+                materials.push(Material::new(&name));
+                continue;
+            }
+
+            let material = materials
+                .last_mut()
+                .ok_or_else(|| parse_error(line, format!("'{}' before any 'newmtl'", keyword)))?;
+
+            match keyword {
+                "Ka" => material.ambient = parse_triple(&mut values, line, "ambient color")?,
+                "Kd" => material.diffuse = parse_triple(&mut values, line, "diffuse color")?,
+                "Ks" => material.specular = parse_triple(&mut values, line, "specular color")?,
+                "Ns" => material.shininess = parse_next(&mut values, line, "specular exponent")?,
+                "d" => material.opacity = parse_next(&mut values, line, "opacity")?,
+                "Tr" => {
+                    material.opacity = 1.0 - parse_next::<f32>(&mut values, line, "transparency")?
+                }
+                "map_Kd" => {
+                    // Texture options may precede the file name, which always comes last
+                    let file = values
+                        .last()
+                        .ok_or_else(|| parse_error(line, "missing texture file name"))?;
+
+                    material.diffuse_map = Some(file.to_owned());
+                }
+                // Other statements, such as illumination models, are not used by the renderer
+                _ => {}
+            }
+        }
+
+        Ok(materials)
+    }
+}
+
+/// A mesh from an OBJ file, containing the faces of one object and group that share a material.
+#[derive(Clone, Debug)]
+pub struct ObjMesh {
+    /// The name of the object the faces belong to, or an empty string if none was given.
+    pub object: String,
+    /// The name of the group the faces belong to, or an empty string if none was given.
+    pub group: String,
+    /// The name of the material used by the faces, if any.
+    pub material: Option<String>,
+    /// The triangles making up the faces.
+    pub mesh: Mesh<3, f32>,
+    /// The vertex normals of each triangle, if every vertex of the face specified one.
+    pub normals: Vec<Option<[Vector<3>; 3]>>,
+    /// The texture coordinates of each triangle, if every vertex of the face specified them.
+    pub uvs: Vec<Option<[Vector<2>; 3]>>,
+}
+
+/// A model loaded from a Wavefront OBJ file.
+#[derive(Clone, Debug, Default)]
+pub struct ObjModel {
+    /// The meshes making up the model, split by object, group and material.
+    pub meshes: Vec<ObjMesh>,
+    /// The names of the material libraries referenced by the model.
+    pub material_libraries: Vec<String>,
+    /// The materials loaded from the model's material libraries, by name.
+    pub materials: HashMap<String, Material>,
+}
+
+/// A face vertex reference, made up of indices into the position, texture coordinate and normal lists.
+type FaceVertex = (usize, Option<usize>, Option<usize>);
+
+// OBJ model implementation
+impl ObjModel {
+    /// Load an OBJ file, along with any material libraries it references. Material libraries are looked up relative to the directory of the OBJ file.
+    ///
+    /// `path`: the path of the OBJ file.
+    ///
+    /// Returns a result containing the loaded model, or the error variant if a file could not be read or parsed.
+    pub fn load<P: AsRef<Path>>(path: P) -> ObjResult<Self> {
+        let path = path.as_ref();
+        let mut model = Self::parse(BufReader::new(File::open(path)?))?;
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+
+        for library in model.material_libraries.clone() {
+            let file = File::open(directory.join(&library))?;
+
+            let materials = Material::parse_library(BufReader::new(file)).map_err(|e| match e {
+                ObjError::ParseError { line, message } => ObjError::MaterialParseError {
+                    library: library.clone(),
+                    line,
+                    message,
+                },
+                e => e,
+            })?;
+
+            for material in materials {
+                model.materials.insert(material.name.clone(), material);
+            }
+        }
+
+        Ok(model)
+    }
+
+    /// Parse an OBJ file. Referenced material libraries are recorded but not loaded.
+    ///
+    /// `reader`: the reader from which the file will be read.
+    ///
+    /// Returns a result containing the parsed model, or the error variant if the file could not be read or parsed.
+    pub fn parse<R: BufRead>(reader: R) -> ObjResult<Self> {
+        let mut model = Self::default();
+        let mut positions: Vec<Vector<3>> = Vec::new();
+        let mut uvs: Vec<Vector<2>> = Vec::new();
+        let mut normals: Vec<Vector<3>> = Vec::new();
+        let mut object = String::new();
+        let mut group = String::new();
+        let mut material: Option<String> = None;
+
+        for (index, text) in reader.lines().enumerate() {
+            let text = text?;
+            let line = index + 1;
+            let text = text.split('#').next().unwrap_or("");
+            let mut values = text.split_whitespace();
+
+            let keyword = match values.next() {
+                Some(keyword) => keyword,
+                None => continue,
+            };
+
+            match keyword {
+                "v" => positions.push(Vector::from(parse_triple(&mut values, line, "position")?)),
+                "vt" => {
+                    let u = parse_next(&mut values, line, "texture coordinate")?;
+                    let v = parse_optional(&mut values, line, "texture coordinate")?;
+                    uvs.push(Vector::from([u, v.unwrap_or(0.0)]));
+                }
+                "vn" => normals.push(Vector::from(parse_triple(&mut values, line, "normal")?)),
+                "f" => {
+                    let face = values
+                        .map(|vertex| {
+                            Self::parse_face_vertex(
+                                vertex,
+                                (positions.len(), uvs.len(), normals.len()),
+                                line,
+                            )
+                        })
+                        .collect::<ObjResult<Vec<_>>>()?;
+
+                    if face.len() < 3 {
+                        return Err(parse_error(line, "face has fewer than three vertices"));
+                    }
+
+                    let mesh = model.current_mesh(&object, &group, &material);
+
+                    // Triangulate the polygon as a fan around its first vertex
+                    for i in 1..face.len() - 1 {
+                        let corners = [face[0], face[i], face[i + 1]];
+
+                        let triangle_normals = corners
+                            .iter()
+                            .all(|(_, _, vn)| vn.is_some())
+                            .then(|| corners.map(|(_, _, vn)| normals[vn.unwrap()]));
+
+                        let mut triangle =
+                            Triangle::new(corners.map(|(v, _, _)| positions[v]), 1.0);
+                        triangle.set_normals(triangle_normals);
+
+                        mesh.mesh.push(triangle);
+                        mesh.uvs.push(
+                            corners
+                                .iter()
+                                .all(|(_, vt, _)| vt.is_some())
+                                .then(|| corners.map(|(_, vt, _)| uvs[vt.unwrap()])),
+                        );
+                        mesh.normals.push(triangle_normals);
+                    }
+                }
+                "o" => object = values.collect::<Vec<_>>().join(" "),
+                "g" => group = values.collect::<Vec<_>>().join(" "),
+                "usemtl" => {
+                    let name = values.collect::<Vec<_>>().join(" ");
+
+                    if name.is_empty() {
+                        return Err(parse_error(line, "missing material name"));
+                    }
+
+                    material = Some(name);
+                }
+                "mtllib" => model
+                    .material_libraries
+                    .extend(values.map(|library| library.to_owned())),
+                // Other statements, such as smoothing groups, lines and free-form geometry, are not supported
+                _ => {}
+            }
+        }
+
+        Ok(model)
+    }
+
+    /// Parse a single vertex reference of a face, in one of the forms `v`, `v/vt`, `v//vn` or `v/vt/vn`.
+    ///
+    /// `vertex`: the vertex reference.
+    /// `counts`: the number of positions, texture coordinates and normals defined so far.
+    /// `line`: the line number of the statement.
+    ///
+    /// Returns a result containing the resolved zero-based indices.
+    fn parse_face_vertex(
+        vertex: &str,
+        counts: (usize, usize, usize),
+        line: usize,
+    ) -> ObjResult<FaceVertex> {
+        let mut parts = vertex.split('/');
+        let position = resolve_index(parts.next().unwrap_or(""), counts.0, line)?;
+
+        let uv = match parts.next() {
+            Some("") | None => None,
+            Some(index) => Some(resolve_index(index, counts.1, line)?),
+        };
+
+        let normal = match parts.next() {
+            Some("") | None => None,
+            Some(index) => Some(resolve_index(index, counts.2, line)?),
+        };
+
+        if parts.next().is_some() {
+            return Err(parse_error(
+                line,
+                format!("invalid face vertex '{}'", vertex),
+            ));
+        }
+
+        Ok((position, uv, normal))
+    }
+
+    /// Get the mesh that new faces should be added to, creating it if the object, group or material has changed.
+    ///
+    /// `object`: the current object name.
+    /// `group`: the current group name.
+    /// `material`: the current material name.
+    ///
+    /// Returns a mutable reference to the mesh.
+    fn current_mesh(
+        &mut self,
+        object: &str,
+        group: &str,
+        material: &Option<String>,
+    ) -> &mut ObjMesh {
+        let matches = self.meshes.last().is_some_and(|mesh| {
+            mesh.object == object && mesh.group == group && &mesh.material == material
+        });
+
+        if !matches {
+            self.meshes.push(ObjMesh {
+                object: object.to_owned(),
+                group: group.to_owned(),
+                material: material.clone(),
+                mesh: Mesh::new(Vec::new()),
+                normals: Vec::new(),
+                uvs: Vec::new(),
+            });
+        }
+
+        self.meshes.last_mut().unwrap()
+    }
+
+    /// Get the material used by a mesh of the model.
+    ///
+    /// `mesh`: the mesh.
+    ///
+    /// Returns an option containing the material, or the none variant if the mesh has no material or it was not loaded.
+    pub fn material(&self, mesh: &ObjMesh) -> Option<&Material> {
+        self.materials.get(mesh.material.as_ref()?)
+    }
+
+    /// Combine all meshes of the model into a single mesh.
+    ///
+    /// Returns the combined mesh.
+    pub fn mesh(&self) -> Mesh<3, f32> {
+        self.meshes
+            .iter()
+            .flat_map(|mesh| mesh.mesh.triangles().iter().copied())
+            .collect()
+    }
+}
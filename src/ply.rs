@@ -0,0 +1,896 @@
+use crate::color::*;
+use crate::indexed_mesh::*;
+use crate::mesh::*;
+use crate::triangle::*;
+use crate::vector::*;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+
+/// An error type for reading PLY files.
+#[derive(Debug)]
+pub enum PlyError {
+    /// An error reading the file.
+    IOError(io::Error),
+    /// A malformed line in the header or in an ASCII body.
+    ParseError {
+        /// The line number, starting from one.
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+    /// Malformed or inconsistent data, such as a truncated binary body or an out of range vertex index.
+    InvalidData(String),
+}
+
+// Convert I/O errors to PLY errors
+impl From<io::Error> for PlyError {
+    fn from(e: io::Error) -> Self {
+        Self::IOError(e)
+    }
+}
+
+// Display PLY errors
+impl fmt::Display for PlyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IOError(e) => write!(f, "{}", e),
+            Self::ParseError { line, message } => write!(f, "line {}: {}", line, message),
+            Self::InvalidData(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// Mark PLY error as an implementation of the standard error trait
+impl error::Error for PlyError {}
+
+/// A result type for reading PLY files.
+pub type PlyResult<T> = Result<T, PlyError>;
+
+/// Create a parse error.
+///
+/// `line`: the line number.
+/// `message`: a description of the problem.
+///
+/// Returns the parse error.
+fn parse_error<S: Into<String>>(line: usize, message: S) -> PlyError {
+    PlyError::ParseError {
+        line,
+        message: message.into(),
+    }
+}
+
+/// A variant of the PLY file format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlyFormat {
+    /// Human readable text.
+    Ascii,
+    /// Little endian binary values.
+    BinaryLittleEndian,
+    /// Big endian binary values.
+    BinaryBigEndian,
+}
+
+// PLY format implementation
+impl PlyFormat {
+    /// Get the name of the format as written in a PLY header.
+    ///
+    /// Returns the format name.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Ascii => "ascii",
+            Self::BinaryLittleEndian => "binary_little_endian",
+            Self::BinaryBigEndian => "binary_big_endian",
+        }
+    }
+}
+
+/// The type of a scalar PLY property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ScalarType {
+    /// A signed 8-bit integer.
+    Int8,
+    /// An unsigned 8-bit integer.
+    UInt8,
+    /// A signed 16-bit integer.
+    Int16,
+    /// An unsigned 16-bit integer.
+    UInt16,
+    /// A signed 32-bit integer.
+    Int32,
+    /// An unsigned 32-bit integer.
+    UInt32,
+    /// A 32-bit floating point number.
+    Float32,
+    /// A 64-bit floating point number.
+    Float64,
+}
+
+// Scalar type implementation
+impl ScalarType {
+    /// Parse a scalar type from its name in a PLY header, accepting both the original and sized names.
+    ///
+    /// `name`: the type name.
+    ///
+    /// Returns an option containing the scalar type, or the none variant if the name is not recognized.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "char" | "int8" => Some(Self::Int8),
+            "uchar" | "uint8" => Some(Self::UInt8),
+            "short" | "int16" => Some(Self::Int16),
+            "ushort" | "uint16" => Some(Self::UInt16),
+            "int" | "int32" => Some(Self::Int32),
+            "uint" | "uint32" => Some(Self::UInt32),
+            "float" | "float32" => Some(Self::Float32),
+            "double" | "float64" => Some(Self::Float64),
+            _ => None,
+        }
+    }
+
+    /// Get the size of a value of this type in a binary body.
+    ///
+    /// Returns the size in bytes.
+    fn size(&self) -> usize {
+        match self {
+            Self::Int8 | Self::UInt8 => 1,
+            Self::Int16 | Self::UInt16 => 2,
+            Self::Int32 | Self::UInt32 | Self::Float32 => 4,
+            Self::Float64 => 8,
+        }
+    }
+
+    /// Check if this is a floating point type.
+    ///
+    /// Returns whether values of this type are floating point.
+    fn is_float(&self) -> bool {
+        matches!(self, Self::Float32 | Self::Float64)
+    }
+
+    /// Decode a binary value of this type.
+    ///
+    /// `bytes`: the value's bytes, which must be exactly the size of the type.
+    /// `big_endian`: whether the value is big endian.
+    ///
+    /// Returns the decoded value.
+    fn decode(&self, bytes: &[u8], big_endian: bool) -> f64 {
+        macro_rules! decode {
+            ($t:ty) => {{
+                let bytes = bytes.try_into().unwrap();
+
+                if big_endian {
+                    <$t>::from_be_bytes(bytes) as f64
+                } else {
+                    <$t>::from_le_bytes(bytes) as f64
+                }
+            }};
+        }
+
+        match self {
+            Self::Int8 => decode!(i8),
+            Self::UInt8 => decode!(u8),
+            Self::Int16 => decode!(i16),
+            Self::UInt16 => decode!(u16),
+            Self::Int32 => decode!(i32),
+            Self::UInt32 => decode!(u32),
+            Self::Float32 => decode!(f32),
+            Self::Float64 => decode!(f64),
+        }
+    }
+}
+
+/// The layout of a PLY property.
+#[derive(Clone, Copy, Debug)]
+enum PropertyKind {
+    /// A single value.
+    Scalar(ScalarType),
+    /// A variable length list of values, preceded by its length.
+    List {
+        /// The type of the list length.
+        count: ScalarType,
+        /// The type of each list item.
+        item: ScalarType,
+    },
+}
+
+/// A property of a PLY element, as declared in the header.
+#[derive(Clone, Debug)]
+struct Property {
+    /// The name of the property.
+    name: String,
+    /// The layout of the property.
+    kind: PropertyKind,
+}
+
+/// An element of a PLY file, as declared in the header.
+#[derive(Clone, Debug)]
+struct Element {
+    /// The name of the element.
+    name: String,
+    /// The number of instances of the element.
+    count: usize,
+    /// The properties of each instance.
+    properties: Vec<Property>,
+}
+
+// Element implementation
+impl Element {
+    /// Find a property of the element by name.
+    ///
+    /// `name`: the property name.
+    ///
+    /// Returns an option containing the index of the property.
+    fn property(&self, name: &str) -> Option<usize> {
+        self.properties
+            .iter()
+            .position(|property| property.name == name)
+    }
+}
+
+/// The decoded value of a single property.
+#[derive(Clone, Debug)]
+enum Value {
+    /// A single value.
+    Scalar(f64),
+    /// A list of values.
+    List(Vec<f64>),
+}
+
+// Value implementation
+impl Value {
+    /// Get the value as a scalar.
+    ///
+    /// Returns the value, or the first item of a list.
+    fn scalar(&self) -> f64 {
+        match self {
+            Self::Scalar(value) => *value,
+            Self::List(values) => values.first().copied().unwrap_or(0.0),
+        }
+    }
+}
+
+/// The parsed header of a PLY file.
+struct Header {
+    /// The format of the body.
+    format: PlyFormat,
+    /// The elements of the file, in order.
+    elements: Vec<Element>,
+    /// The number of lines making up the header.
+    lines: usize,
+    /// The offset of the body in bytes.
+    body_offset: usize,
+}
+
+/// Parse the header of a PLY file.
+///
+/// `data`: the contents of the file.
+///
+/// Returns a result containing the header.
+fn parse_header(data: &[u8]) -> PlyResult<Header> {
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+    let mut offset = 0;
+    let mut line = 0;
+
+    loop {
+        let end = data[offset..]
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .ok_or_else(|| parse_error(line + 1, "missing 'end_header'"))?;
+        let text = std::str::from_utf8(&data[offset..offset + end])
+            .map_err(|_| parse_error(line + 1, "invalid text encoding in header"))?;
+
+        offset += end + 1;
+        line += 1;
+
+        let mut values = text.split_whitespace();
+        let keyword = values.next().unwrap_or("");
+
+        if line == 1 {
+            if keyword != "ply" {
+                return Err(parse_error(line, "not a PLY file"));
+            }
+
+            continue;
+        }
+
+        match keyword {
+            "format" => {
+                format = Some(match values.next() {
+                    Some("ascii") => PlyFormat::Ascii,
+                    Some("binary_little_endian") => PlyFormat::BinaryLittleEndian,
+                    Some("binary_big_endian") => PlyFormat::BinaryBigEndian,
+                    other => {
+                        return Err(parse_error(
+                            line,
+                            format!("unknown format '{}'", other.unwrap_or("")),
+                        ))
+                    }
+                });
+            }
+            "element" => {
+                let name = values
+                    .next()
+                    .ok_or_else(|| parse_error(line, "missing element name"))?;
+                let count = values
+                    .next()
+                    .and_then(|count| count.parse().ok())
+                    .ok_or_else(|| parse_error(line, "missing or invalid element count"))?;
+
+                elements.push(Element {
+                    name: name.to_owned(),
+                    count,
+                    properties: Vec::new(),
+                });
+            }
+            "property" => {
+                let element = elements
+                    .last_mut()
+                    .ok_or_else(|| parse_error(line, "property before any element"))?;
+                let scalar = |name: Option<&str>| {
+                    let name = name.unwrap_or("");
+
+                    ScalarType::from_name(name)
+                        .ok_or_else(|| parse_error(line, format!("unknown type '{}'", name)))
+                };
+
+                let kind = match values.next() {
+                    Some("list") => PropertyKind::List {
+                        count: scalar(values.next())?,
+                        item: scalar(values.next())?,
+                    },
+                    name => PropertyKind::Scalar(scalar(name)?),
+                };
+
+                let name = values
+                    .next()
+                    .ok_or_else(|| parse_error(line, "missing property name"))?;
+
+                element.properties.push(Property {
+                    name: name.to_owned(),
+                    kind,
+                });
+            }
+            "end_header" => break,
+            "comment" | "obj_info" | "" => {}
+            other => return Err(parse_error(line, format!("unknown keyword '{}'", other))),
+        }
+    }
+
+    Ok(Header {
+        format: format.ok_or_else(|| parse_error(line, "missing format"))?,
+        elements,
+        lines: line,
+        body_offset: offset,
+    })
+}
+
+/// A reader over the body of a PLY file.
+enum BodyReader<'a> {
+    /// An ASCII body, with one element instance per line.
+    Ascii {
+        /// The remaining lines of the body, along with their line numbers.
+        lines: Box<dyn Iterator<Item = (usize, &'a str)> + 'a>,
+        /// The remaining values on the current line.
+        values: std::str::SplitWhitespace<'a>,
+        /// The current line number.
+        line: usize,
+    },
+    /// A binary body.
+    Binary {
+        /// The body data.
+        data: &'a [u8],
+        /// The offset of the next value.
+        offset: usize,
+        /// Whether values are big endian.
+        big_endian: bool,
+    },
+}
+
+// Body reader implementation
+impl<'a> BodyReader<'a> {
+    /// Create a reader over the body of a PLY file.
+    ///
+    /// `data`: the contents of the file.
+    /// `header`: the parsed header of the file.
+    ///
+    /// Returns a result containing the reader.
+    fn new(data: &'a [u8], header: &Header) -> PlyResult<Self> {
+        let body = &data[header.body_offset..];
+        let first_line = header.lines + 1;
+
+        Ok(match header.format {
+            PlyFormat::Ascii => {
+                let text = std::str::from_utf8(body)
+                    .map_err(|_| parse_error(first_line, "invalid text encoding in body"))?;
+
+                Self::Ascii {
+                    lines: Box::new(
+                        text.lines()
+                            .enumerate()
+                            .map(move |(index, line)| (first_line + index, line)),
+                    ),
+                    values: "".split_whitespace(),
+                    line: header.lines,
+                }
+            }
+            format => Self::Binary {
+                data: body,
+                offset: 0,
+                big_endian: format == PlyFormat::BinaryBigEndian,
+            },
+        })
+    }
+
+    /// Begin reading a new element instance. In an ASCII body this moves to the next non-empty line.
+    ///
+    /// `element`: the name of the element, used in error messages.
+    ///
+    /// Returns a result of the operation.
+    fn begin_instance(&mut self, element: &str) -> PlyResult<()> {
+        if let Self::Ascii {
+            lines,
+            values,
+            line,
+        } = self
+        {
+            loop {
+                let (number, text) = lines.next().ok_or_else(|| {
+                    PlyError::InvalidData(format!("unexpected end of file reading '{}'", element))
+                })?;
+
+                *line = number;
+                *values = text.split_whitespace();
+
+                if !text.trim().is_empty() {
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read a single scalar value.
+    ///
+    /// `kind`: the type of the value.
+    ///
+    /// Returns a result containing the value.
+    fn scalar(&mut self, kind: ScalarType) -> PlyResult<f64> {
+        match self {
+            Self::Ascii { values, line, .. } => {
+                let value = values
+                    .next()
+                    .ok_or_else(|| parse_error(*line, "too few values"))?;
+
+                value
+                    .parse()
+                    .map_err(|_| parse_error(*line, format!("invalid value '{}'", value)))
+            }
+            Self::Binary {
+                data,
+                offset,
+                big_endian,
+            } => {
+                let bytes = data.get(*offset..*offset + kind.size()).ok_or_else(|| {
+                    PlyError::InvalidData("unexpected end of binary data".to_owned())
+                })?;
+
+                *offset += kind.size();
+
+                Ok(kind.decode(bytes, *big_endian))
+            }
+        }
+    }
+
+    /// Read the value of a property.
+    ///
+    /// `kind`: the layout of the property.
+    ///
+    /// Returns a result containing the value.
+    fn value(&mut self, kind: PropertyKind) -> PlyResult<Value> {
+        match kind {
+            PropertyKind::Scalar(scalar) => Ok(Value::Scalar(self.scalar(scalar)?)),
+            PropertyKind::List { count, item } => {
+                let length = self.scalar(count)?;
+
+                if length < 0.0 || length.fract() != 0.0 {
+                    return Err(PlyError::InvalidData(format!(
+                        "invalid list length {}",
+                        length
+                    )));
+                }
+
+                (0..length as usize)
+                    .map(|_| self.scalar(item))
+                    .collect::<PlyResult<_>>()
+                    .map(Value::List)
+            }
+        }
+    }
+}
+
+/// A single value written to the body of a PLY file.
+#[derive(Clone, Copy, Debug)]
+enum RowValue {
+    /// A 32-bit floating point number.
+    Float32(f32),
+    /// An unsigned 8-bit integer.
+    UInt8(u8),
+    /// An unsigned 32-bit integer.
+    UInt32(u32),
+}
+
+/// Write the values of a single element instance to the body of a PLY file.
+///
+/// `writer`: the writer to which the values will be written.
+/// `format`: the PLY variant to write.
+/// `row`: the values of the instance's properties.
+///
+/// Returns a result of the write operation.
+fn write_row<W: Write>(writer: &mut W, format: PlyFormat, row: &[RowValue]) -> io::Result<()> {
+    if format == PlyFormat::Ascii {
+        let text = row
+            .iter()
+            .map(|value| match value {
+                RowValue::Float32(value) => value.to_string(),
+                RowValue::UInt8(value) => value.to_string(),
+                RowValue::UInt32(value) => value.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        return writeln!(writer, "{}", text);
+    }
+
+    let big_endian = format == PlyFormat::BinaryBigEndian;
+
+    for value in row {
+        match (value, big_endian) {
+            (RowValue::Float32(value), false) => writer.write_all(&value.to_le_bytes())?,
+            (RowValue::Float32(value), true) => writer.write_all(&value.to_be_bytes())?,
+            (RowValue::UInt8(value), _) => writer.write_all(&[*value])?,
+            (RowValue::UInt32(value), false) => writer.write_all(&value.to_le_bytes())?,
+            (RowValue::UInt32(value), true) => writer.write_all(&value.to_be_bytes())?,
+        }
+    }
+
+    Ok(())
+}
+
+/// A model loaded from a PLY file, made up of vertices with optional attributes and triangular faces indexing into them.
+#[derive(Clone, Debug, Default)]
+pub struct PlyModel {
+    /// The position of each vertex.
+    pub positions: Vec<Vector<3>>,
+    /// The normal of each vertex, if the file has normals.
+    pub normals: Option<Vec<Vector<3>>>,
+    /// The color of each vertex, if the file has colors.
+    pub colors: Option<Vec<Color>>,
+    /// The vertex indices of each triangle. Faces with more than three vertices are triangulated as fans, and point clouds have no faces.
+    pub faces: Vec<[usize; 3]>,
+}
+
+// PLY model implementation
+impl PlyModel {
+    /// Read a PLY file in any of its formats. Elements other than vertices and faces are skipped.
+    ///
+    /// `reader`: the reader from which the file will be read.
+    ///
+    /// Returns a result containing the model, or the error variant if the file could not be read or parsed.
+    pub fn read<R: Read>(mut reader: R) -> PlyResult<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let header = parse_header(&data)?;
+        let mut body = BodyReader::new(&data, &header)?;
+        let mut model = Self::default();
+
+        for element in &header.elements {
+            for _ in 0..element.count {
+                body.begin_instance(&element.name)?;
+
+                let values = element
+                    .properties
+                    .iter()
+                    .map(|property| body.value(property.kind))
+                    .collect::<PlyResult<Vec<_>>>()?;
+
+                match element.name.as_str() {
+                    "vertex" => model.push_vertex(element, &values)?,
+                    "face" => model.push_face(element, &values)?,
+                    _ => {}
+                }
+            }
+        }
+
+        let count = model.positions.len();
+
+        if let Some(index) = model.faces.iter().flatten().find(|&&index| index >= count) {
+            return Err(PlyError::InvalidData(format!(
+                "vertex index {} out of range, only {} vertices",
+                index, count
+            )));
+        }
+
+        Ok(model)
+    }
+
+    /// Load a PLY file in any of its formats.
+    ///
+    /// `path`: the path of the PLY file.
+    ///
+    /// Returns a result containing the model, or the error variant if the file could not be read or parsed.
+    pub fn load<P: AsRef<Path>>(path: P) -> PlyResult<Self> {
+        Self::read(File::open(path)?)
+    }
+
+    /// Add a vertex from a vertex element instance.
+    ///
+    /// `element`: the vertex element declaration.
+    /// `values`: the values of the instance's properties.
+    ///
+    /// Returns a result of the operation, or the error variant if the element has no position.
+    fn push_vertex(&mut self, element: &Element, values: &[Value]) -> PlyResult<()> {
+        let find = |names: [&str; 3]| {
+            let indices = names.map(|name| element.property(name));
+
+            indices
+                .iter()
+                .all(|index| index.is_some())
+                .then(|| indices.map(|index| index.unwrap()))
+        };
+
+        let position = find(["x", "y", "z"]).ok_or_else(|| {
+            PlyError::InvalidData("vertex element has no x, y and z properties".to_owned())
+        })?;
+        self.positions
+            .push(Vector::from(position.map(|i| values[i].scalar() as f32)));
+
+        if let Some(normal) = find(["nx", "ny", "nz"]) {
+            self.normals
+                .get_or_insert_with(Vec::new)
+                .push(Vector::from(normal.map(|i| values[i].scalar() as f32)));
+        }
+
+        if let Some(color) = find(["red", "green", "blue"]) {
+            let [r, g, b] = color.map(|i| {
+                let value = values[i].scalar();
+
+                // Floating point colors range from zero to one rather than zero to 255
+                let value = match element.properties[i].kind {
+                    PropertyKind::Scalar(kind) if kind.is_float() => value * 255.0,
+                    _ => value,
+                };
+
+                value.round().clamp(0.0, 255.0) as u8
+            });
+
+            self.colors
+                .get_or_insert_with(Vec::new)
+                .push(Color::new(r, g, b));
+        }
+
+        Ok(())
+    }
+
+    /// Add the triangles of a face element instance.
+    ///
+    /// `element`: the face element declaration.
+    /// `values`: the values of the instance's properties.
+    ///
+    /// Returns a result of the operation, or the error variant if the element has no vertex index list or the face has fewer than three vertices.
+    fn push_face(&mut self, element: &Element, values: &[Value]) -> PlyResult<()> {
+        let property = element
+            .property("vertex_indices")
+            .or_else(|| element.property("vertex_index"))
+            .ok_or_else(|| {
+                PlyError::InvalidData("face element has no vertex index list".to_owned())
+            })?;
+
+        let indices = match &values[property] {
+            Value::List(indices) => indices,
+            Value::Scalar(_) => {
+                return Err(PlyError::InvalidData(
+                    "face vertex indices are not a list".to_owned(),
+                ))
+            }
+        };
+
+        if indices.len() < 3 {
+            return Err(PlyError::InvalidData(format!(
+                "face has {} vertices, at least three are required",
+                indices.len()
+            )));
+        }
+
+        if let Some(index) = indices
+            .iter()
+            .find(|&&index| index < 0.0 || index.fract() != 0.0)
+        {
+            return Err(PlyError::InvalidData(format!(
+                "invalid vertex index {}",
+                index
+            )));
+        }
+
+        // Triangulate the polygon as a fan around its first vertex
+        for i in 1..indices.len() - 1 {
+            self.faces
+                .push([indices[0], indices[i], indices[i + 1]].map(|index| index as usize));
+        }
+
+        Ok(())
+    }
+
+    /// Convert the model's faces into a mesh, keeping its vertex normals.
+    ///
+    /// Returns the mesh.
+    pub fn mesh(&self) -> Mesh<3, f32> {
+        self.faces
+            .iter()
+            .map(|face| {
+                let mut triangle = Triangle::new(face.map(|index| self.positions[index]), 1.0);
+                triangle.set_normals(
+                    self.normals
+                        .as_ref()
+                        .map(|normals| face.map(|index| normals[index])),
+                );
+                triangle
+            })
+            .collect()
+    }
+
+    /// Convert the model into an indexed mesh, keeping its vertex normals and colors.
+    ///
+    /// Returns a result containing the indexed mesh, or the error variant if a face index is out of range or the normals or colors do not match the vertices.
+    pub fn indexed_mesh(&self) -> PlyResult<IndexedMesh<3, f32>> {
+        let invalid = |e: IndexedMeshError| PlyError::InvalidData(e.to_string());
+
+        let mut mesh =
+            IndexedMesh::try_new(self.positions.clone(), self.faces.clone()).map_err(invalid)?;
+        mesh.try_set_normals(self.normals.clone())
+            .map_err(invalid)?;
+        mesh.try_set_colors(self.colors.clone()).map_err(invalid)?;
+
+        Ok(mesh)
+    }
+
+    /// Write the model as a PLY file.
+    ///
+    /// `writer`: the writer to which the file will be written.
+    /// `format`: the PLY variant to write.
+    ///
+    /// Returns a result of the write operation.
+    pub fn write<W: Write>(&self, mut writer: W, format: PlyFormat) -> io::Result<()> {
+        let count = self.positions.len();
+        let normals = self
+            .normals
+            .as_ref()
+            .filter(|normals| normals.len() == count);
+        let colors = self.colors.as_ref().filter(|colors| colors.len() == count);
+
+        writeln!(writer, "ply")?;
+        writeln!(writer, "format {} 1.0", format.name())?;
+        writeln!(writer, "comment written by render-3d")?;
+        writeln!(writer, "element vertex {}", count)?;
+        writeln!(writer, "property float x")?;
+        writeln!(writer, "property float y")?;
+        writeln!(writer, "property float z")?;
+
+        if normals.is_some() {
+            writeln!(writer, "property float nx")?;
+            writeln!(writer, "property float ny")?;
+            writeln!(writer, "property float nz")?;
+        }
+
+        if colors.is_some() {
+            writeln!(writer, "property uchar red")?;
+            writeln!(writer, "property uchar green")?;
+            writeln!(writer, "property uchar blue")?;
+        }
+
+        writeln!(writer, "element face {}", self.faces.len())?;
+        writeln!(writer, "property list uchar uint vertex_indices")?;
+        writeln!(writer, "end_header")?;
+
+        for i in 0..count {
+            let mut row: Vec<_> = self.positions[i]
+                .coords()
+                .iter()
+                .map(|&value| RowValue::Float32(value))
+                .collect();
+
+            if let Some(normals) = normals {
+                row.extend(
+                    normals[i]
+                        .coords()
+                        .iter()
+                        .map(|&value| RowValue::Float32(value)),
+                );
+            }
+
+            if let Some(colors) = colors {
+                let (r, g, b) = colors[i].into();
+                row.extend([r, g, b].map(RowValue::UInt8));
+            }
+
+            write_row(&mut writer, format, &row)?;
+        }
+
+        for face in &self.faces {
+            let mut row = vec![RowValue::UInt8(3)];
+
+            for &vertex in face {
+                row.push(RowValue::UInt32(u32::try_from(vertex).map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "too many vertices for a PLY file",
+                    )
+                })?));
+            }
+
+            write_row(&mut writer, format, &row)?;
+        }
+
+        writer.flush()
+    }
+
+    /// Save the model to a PLY file.
+    ///
+    /// `path`: the path of the PLY file.
+    /// `format`: the PLY variant to write.
+    ///
+    /// Returns a result of the save operation.
+    pub fn save<P: AsRef<Path>>(&self, path: P, format: PlyFormat) -> io::Result<()> {
+        self.write(BufWriter::new(File::create(path)?), format)
+    }
+}
+
+// Convert a mesh into a PLY model, merging identical vertices
+impl From<&Mesh<3, f32>> for PlyModel {
+    fn from(mesh: &Mesh<3, f32>) -> Self {
+        let mut model = Self::default();
+        let mut indices: HashMap<[u32; 3], usize> = HashMap::new();
+
+        for triangle in mesh.triangles() {
+            let face = triangle.vectors().map(|vector| {
+                let key = vector.coords().map(|value| value.to_bits());
+
+                *indices.entry(key).or_insert_with(|| {
+                    model.positions.push(vector);
+                    model.positions.len() - 1
+                })
+            });
+
+            model.faces.push(face);
+        }
+
+        model
+    }
+}
+
+// PLY encoding for meshes
+impl Mesh<3, f32> {
+    /// Write the mesh as a PLY file, merging identical vertices.
+    ///
+    /// `writer`: the writer to which the file will be written.
+    /// `format`: the PLY variant to write.
+    ///
+    /// Returns a result of the write operation.
+    pub fn write_ply<W: Write>(&self, writer: W, format: PlyFormat) -> io::Result<()> {
+        PlyModel::from(self).write(writer, format)
+    }
+
+    /// Save the mesh to a PLY file, merging identical vertices.
+    ///
+    /// `path`: the path of the PLY file.
+    /// `format`: the PLY variant to write.
+    ///
+    /// Returns a result of the save operation.
+    pub fn save_ply<P: AsRef<Path>>(&self, path: P, format: PlyFormat) -> io::Result<()> {
+        self.write_ply(BufWriter::new(File::create(path)?), format)
+    }
+}
@@ -0,0 +1,345 @@
+use crate::indexed_mesh::*;
+use crate::mesh::*;
+use crate::triangle::*;
+use crate::vector::*;
+use std::collections::HashMap;
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+/// Check if a triangle has no area, such as the triangles collapsing onto the poles of a sphere.
+///
+/// `vecs`: the triangle's vectors.
+///
+/// Returns whether the triangle is degenerate.
+fn is_degenerate(vecs: &[Vector<3>; 3]) -> bool {
+    let a = vecs[1] - vecs[0];
+    let b = vecs[2] - vecs[0];
+    let scale = a.length_squared().max(b.length_squared());
+
+    a.cross(&b).length_squared() <= scale * scale * 1e-12
+}
+
+/// Generate a subdivided parallelogram. The triangles face the direction of the cross product of the two edges.
+///
+/// `origin`: the corner of the parallelogram.
+/// `u`: the first edge of the parallelogram.
+/// `v`: the second edge of the parallelogram.
+/// `segments`: the number of segments along each edge.
+///
+/// Returns the triangles of the parallelogram.
+fn grid(
+    origin: Vector<3>,
+    u: Vector<3>,
+    v: Vector<3>,
+    segments: [usize; 2],
+) -> Vec<Triangle<3, f32>> {
+    let [nu, nv] = segments.map(|count| count.max(1));
+    let point =
+        |i: usize, j: usize| origin + u * (i as f32 / nu as f32) + v * (j as f32 / nv as f32);
+    let mut tris = Vec::with_capacity(nu * nv * 2);
+
+    for i in 0..nu {
+        for j in 0..nv {
+            let corners = [
+                point(i, j),
+                point(i + 1, j),
+                point(i + 1, j + 1),
+                point(i, j + 1),
+            ];
+
+            tris.push(Triangle::new([corners[0], corners[1], corners[2]], 1.0));
+            tris.push(Triangle::new([corners[0], corners[2], corners[3]], 1.0));
+        }
+    }
+
+    tris
+}
+
+/// Generate a surface of revolution by sweeping a profile around the y-axis. Profiles running downwards along their outer side produce outward facing triangles, and triangles collapsing onto the axis are skipped.
+///
+/// `profile`: the profile points, each made up of a distance from the y-axis and a height.
+/// `segments`: the number of segments around the y-axis.
+///
+/// Returns the triangles of the surface.
+fn revolve(profile: &[(f32, f32)], segments: usize) -> Vec<Triangle<3, f32>> {
+    let segments = segments.max(3);
+    let point = |i: usize, k: usize| {
+        let (radius, y) = profile[k];
+        // Wrap the last segment around onto the first, so the seam's vectors match exactly
+        let (sin, cos) = (TAU * (i % segments) as f32 / segments as f32).sin_cos();
+
+        Vector::from([radius * cos, y, radius * sin])
+    };
+
+    let mut tris = Vec::new();
+
+    for i in 0..segments {
+        for k in 0..profile.len().saturating_sub(1) {
+            let corners = [
+                point(i, k),
+                point(i + 1, k),
+                point(i + 1, k + 1),
+                point(i, k + 1),
+            ];
+
+            for vecs in [
+                [corners[0], corners[1], corners[2]],
+                [corners[0], corners[2], corners[3]],
+            ] {
+                if !is_degenerate(&vecs) {
+                    tris.push(Triangle::new(vecs, 1.0));
+                }
+            }
+        }
+    }
+
+    tris
+}
+
+/// Generate the profile of a circular arc, from the top of a sphere downwards.
+///
+/// `radius`: the radius of the arc.
+/// `center`: the height of the arc's center.
+/// `start`: the starting polar angle in radians, measured from the top.
+/// `end`: the ending polar angle in radians.
+/// `rings`: the number of segments along the arc.
+///
+/// Returns the profile points, including both ends of the arc.
+fn arc_profile(radius: f32, center: f32, start: f32, end: f32, rings: usize) -> Vec<(f32, f32)> {
+    (0..=rings)
+        .map(|j| {
+            let angle = start + (end - start) * j as f32 / rings as f32;
+
+            // Keep the poles exactly on the axis
+            if angle == 0.0 || angle == PI {
+                (0.0, center + radius * angle.cos())
+            } else {
+                (radius * angle.sin(), center + radius * angle.cos())
+            }
+        })
+        .collect()
+}
+
+// Procedural primitive generation for meshes
+impl Mesh<3, f32> {
+    /// Generate an axis-aligned box centered on the origin.
+    ///
+    /// `size`: the size of the box along each axis.
+    /// `segments`: the number of segments along each axis.
+    ///
+    /// Returns the box mesh.
+    pub fn cuboid(size: [f32; 3], segments: [usize; 3]) -> Self {
+        let [hx, hy, hz] = size.map(|value| value / 2.0);
+        let [sx, sy, sz] = segments;
+        let x = Vector::from([size[0], 0.0, 0.0]);
+        let y = Vector::from([0.0, size[1], 0.0]);
+        let z = Vector::from([0.0, 0.0, size[2]]);
+        let corner = Vector::from([-hx, -hy, -hz]);
+
+        [
+            grid(Vector::from([hx, -hy, -hz]), y, z, [sy, sz]),
+            grid(corner, z, y, [sz, sy]),
+            grid(Vector::from([-hx, hy, -hz]), z, x, [sz, sx]),
+            grid(corner, x, z, [sx, sz]),
+            grid(Vector::from([-hx, -hy, hz]), x, y, [sx, sy]),
+            grid(corner, y, x, [sy, sx]),
+        ]
+        .concat()
+        .into_iter()
+        .collect()
+    }
+
+    /// Generate a flat grid in the xz-plane centered on the origin, facing the positive y-axis.
+    ///
+    /// `size`: the size of the plane along the x and z axes.
+    /// `segments`: the number of segments along the x and z axes.
+    ///
+    /// Returns the plane mesh.
+    pub fn plane(size: [f32; 2], segments: [usize; 2]) -> Self {
+        let [width, depth] = size;
+
+        Self::new(grid(
+            Vector::from([-width / 2.0, 0.0, -depth / 2.0]),
+            Vector::from([0.0, 0.0, depth]),
+            Vector::from([width, 0.0, 0.0]),
+            [segments[1], segments[0]],
+        ))
+    }
+
+    /// Generate a flat disc in the xz-plane centered on the origin, facing the positive y-axis.
+    ///
+    /// `radius`: the radius of the disc.
+    /// `segments`: the number of segments around the disc.
+    ///
+    /// Returns the disc mesh.
+    pub fn disc(radius: f32, segments: usize) -> Self {
+        Self::new(revolve(&[(0.0, 0.0), (radius, 0.0)], segments))
+    }
+
+    /// Generate a sphere centered on the origin from rings of latitude and segments of longitude, with poles on the y-axis.
+    ///
+    /// `radius`: the radius of the sphere.
+    /// `segments`: the number of segments around the y-axis.
+    /// `rings`: the number of rings from pole to pole.
+    ///
+    /// Returns the sphere mesh.
+    pub fn uv_sphere(radius: f32, segments: usize, rings: usize) -> Self {
+        Self::new(revolve(
+            &arc_profile(radius, 0.0, 0.0, PI, rings.max(2)),
+            segments,
+        ))
+    }
+
+    /// Generate a sphere centered on the origin by repeatedly subdividing an icosahedron, giving evenly sized triangles.
+    ///
+    /// `radius`: the radius of the sphere.
+    /// `subdivisions`: the number of times each triangle is split into four.
+    ///
+    /// Returns the sphere mesh.
+    pub fn icosphere(radius: f32, subdivisions: usize) -> Self {
+        let t = (1.0 + 5.0f32.sqrt()) / 2.0;
+
+        let mut vertices: Vec<Vector<3>> = [
+            [-1.0, t, 0.0],
+            [1.0, t, 0.0],
+            [-1.0, -t, 0.0],
+            [1.0, -t, 0.0],
+            [0.0, -1.0, t],
+            [0.0, 1.0, t],
+            [0.0, -1.0, -t],
+            [0.0, 1.0, -t],
+            [t, 0.0, -1.0],
+            [t, 0.0, 1.0],
+            [-t, 0.0, -1.0],
+            [-t, 0.0, 1.0],
+        ]
+        .into_iter()
+        .map(|vertex| Vector::from(vertex).normalize())
+        .collect();
+
+        let mut faces: Vec<[usize; 3]> = vec![
+            [0, 11, 5],
+            [0, 5, 1],
+            [0, 1, 7],
+            [0, 7, 10],
+            [0, 10, 11],
+            [1, 5, 9],
+            [5, 11, 4],
+            [11, 10, 2],
+            [10, 7, 6],
+            [7, 1, 8],
+            [3, 9, 4],
+            [3, 4, 2],
+            [3, 2, 6],
+            [3, 6, 8],
+            [3, 8, 9],
+            [4, 9, 5],
+            [2, 4, 11],
+            [6, 2, 10],
+            [8, 6, 7],
+            [9, 8, 1],
+        ];
+
+        for _ in 0..subdivisions {
+            let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+            let mut midpoint = |a: usize, b: usize| {
+                *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                    vertices.push(vertices[a].lerp(&vertices[b], 0.5).normalize());
+                    vertices.len() - 1
+                })
+            };
+
+            faces = faces
+                .iter()
+                .flat_map(|&[a, b, c]| {
+                    let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+
+                    [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+                })
+                .collect();
+        }
+
+        IndexedMesh::new(
+            vertices.into_iter().map(|vertex| vertex * radius).collect(),
+            faces,
+        )
+        .to_mesh()
+    }
+
+    /// Generate a capped cylinder centered on the origin, with its axis along the y-axis.
+    ///
+    /// `radius`: the radius of the cylinder.
+    /// `height`: the height of the cylinder.
+    /// `segments`: the number of segments around the y-axis.
+    ///
+    /// Returns the cylinder mesh.
+    pub fn cylinder(radius: f32, height: f32, segments: usize) -> Self {
+        let half = height / 2.0;
+
+        Self::new(revolve(
+            &[(0.0, half), (radius, half), (radius, -half), (0.0, -half)],
+            segments,
+        ))
+    }
+
+    /// Generate a capped cone centered on the origin, with its base below its apex along the y-axis.
+    ///
+    /// `radius`: the radius of the base.
+    /// `height`: the height from the base to the apex.
+    /// `segments`: the number of segments around the y-axis.
+    ///
+    /// Returns the cone mesh.
+    pub fn cone(radius: f32, height: f32, segments: usize) -> Self {
+        let half = height / 2.0;
+
+        Self::new(revolve(
+            &[(0.0, half), (radius, -half), (0.0, -half)],
+            segments,
+        ))
+    }
+
+    /// Generate a torus centered on the origin, lying in the xz-plane.
+    ///
+    /// `major_radius`: the distance from the center of the torus to the center of its tube.
+    /// `minor_radius`: the radius of the tube.
+    /// `major_segments`: the number of segments around the y-axis.
+    /// `minor_segments`: the number of segments around the tube.
+    ///
+    /// Returns the torus mesh.
+    pub fn torus(
+        major_radius: f32,
+        minor_radius: f32,
+        major_segments: usize,
+        minor_segments: usize,
+    ) -> Self {
+        let minor_segments = minor_segments.max(3);
+
+        // Trace the tube's cross section downwards along its outer side
+        let profile: Vec<_> = (0..=minor_segments)
+            .map(|j| {
+                let (sin, cos) = (TAU * j as f32 / minor_segments as f32).sin_cos();
+
+                (major_radius + minor_radius * cos, -minor_radius * sin)
+            })
+            .collect();
+
+        Self::new(revolve(&profile, major_segments))
+    }
+
+    /// Generate a capsule centered on the origin, made up of a cylinder with hemispherical ends along the y-axis.
+    ///
+    /// `radius`: the radius of the cylinder and hemispheres.
+    /// `height`: the height of the cylindrical part, excluding the hemispheres.
+    /// `segments`: the number of segments around the y-axis.
+    /// `rings`: the number of rings in each hemisphere.
+    ///
+    /// Returns the capsule mesh.
+    pub fn capsule(radius: f32, height: f32, segments: usize, rings: usize) -> Self {
+        let half = height / 2.0;
+        let rings = rings.max(1);
+
+        let mut profile = arc_profile(radius, half, 0.0, FRAC_PI_2, rings);
+        profile.extend(arc_profile(radius, -half, FRAC_PI_2, PI, rings));
+
+        Self::new(revolve(&profile, segments))
+    }
+}
@@ -0,0 +1,229 @@
+use crate::matrix::*;
+use crate::vector::*;
+use std::ops::Mul;
+
+/// A quaternion, used to represent rotations in 3D space without gimbal lock.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion {
+    /// The scalar part of the quaternion.
+    w: f32,
+    /// The x component of the vector part of the quaternion.
+    x: f32,
+    /// The y component of the vector part of the quaternion.
+    y: f32,
+    /// The z component of the vector part of the quaternion.
+    z: f32,
+}
+
+// Quaternion implementation
+impl Quaternion {
+    /// Create a new quaternion from its components.
+    ///
+    /// `w`: the scalar part.
+    /// `x`: the x component of the vector part.
+    /// `y`: the y component of the vector part.
+    /// `z`: the z component of the vector part.
+    ///
+    /// Returns the new quaternion.
+    pub fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// Create the identity quaternion, representing no rotation.
+    ///
+    /// Returns the identity quaternion.
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Create a quaternion representing a rotation about an axis.
+    ///
+    /// `axis`: the axis of rotation, which does not need to be normalized.
+    /// `angle`: the angle of rotation in radians, following the right-hand rule.
+    ///
+    /// Returns the new rotation quaternion.
+    pub fn from_axis_angle<V: Into<Vector<3>>>(axis: V, angle: f32) -> Self {
+        let axis = axis.into().normalize();
+        let (sin, cos) = (angle / 2.0).sin_cos();
+
+        Self::new(cos, axis[0] * sin, axis[1] * sin, axis[2] * sin)
+    }
+
+    /// Create a quaternion from Euler angles. The resulting rotation is equivalent to rotating about the x-axis, then the y-axis, then the z-axis, matching `rotate_x(x).rotate_y(y).rotate_z(z)` on a transformation.
+    ///
+    /// `x`: the angle of rotation in radians around the x-axis.
+    /// `y`: the angle of rotation in radians around the y-axis.
+    /// `z`: the angle of rotation in radians around the z-axis.
+    ///
+    /// Returns the new rotation quaternion.
+    pub fn from_euler(x: f32, y: f32, z: f32) -> Self {
+        Self::from_axis_angle([0.0, 0.0, 1.0], z)
+            * Self::from_axis_angle([0.0, 1.0, 0.0], y)
+            * Self::from_axis_angle([1.0, 0.0, 0.0], x)
+    }
+
+    /// Get the components of the quaternion.
+    ///
+    /// Returns the scalar part followed by the x, y and z components of the vector part.
+    pub fn components(&self) -> [f32; 4] {
+        [self.w, self.x, self.y, self.z]
+    }
+
+    /// Compute the dot product of two quaternions.
+    ///
+    /// `other`: the other quaternion.
+    ///
+    /// Returns the resulting dot product.
+    pub fn dot(&self, other: &Self) -> f32 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Get the length of the quaternion.
+    ///
+    /// Returns the quaternion's length.
+    pub fn length(&self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    /// Normalize the quaternion. Only normalized quaternions represent rotations.
+    ///
+    /// Returns the normalized quaternion.
+    pub fn normalize(&self) -> Self {
+        let length = self.length();
+
+        Self::new(
+            self.w / length,
+            self.x / length,
+            self.y / length,
+            self.z / length,
+        )
+    }
+
+    /// Get the conjugate of the quaternion. For normalized quaternions, this is the inverse rotation.
+    ///
+    /// Returns the conjugate quaternion.
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    /// Get the inverse of the quaternion.
+    ///
+    /// Returns an option containing the inverse quaternion, or the none variant if the quaternion has a length of zero.
+    pub fn inverse(&self) -> Option<Self> {
+        let length_squared = self.dot(self);
+
+        if length_squared == 0.0 {
+            return None;
+        }
+
+        let conjugate = self.conjugate();
+
+        Some(Self::new(
+            conjugate.w / length_squared,
+            conjugate.x / length_squared,
+            conjugate.y / length_squared,
+            conjugate.z / length_squared,
+        ))
+    }
+
+    /// Rotate a vector by the quaternion, which is assumed to be normalized.
+    ///
+    /// `vector`: the vector to rotate.
+    ///
+    /// Returns the rotated vector.
+    pub fn rotate_vector<V: Into<Vector<3>>>(&self, vector: V) -> Vector<3> {
+        let vector = vector.into();
+        let axis = Vector::from([self.x, self.y, self.z]);
+        let t = axis.cross(&vector) * 2.0;
+
+        vector + t * self.w + axis.cross(&t)
+    }
+
+    /// Convert the quaternion into a rotation matrix. The quaternion is assumed to be normalized.
+    ///
+    /// Returns the 4x4 rotation matrix.
+    pub fn to_matrix(&self) -> Matrix<4, 4, f32> {
+        let Self { w, x, y, z } = *self;
+
+        Matrix::from_rows([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+                0.0,
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+                0.0,
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+                0.0,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Spherically interpolate between two rotations, following the shortest path at a constant angular velocity.
+    ///
+    /// `other`: the rotation to interpolate towards.
+    /// `t`: the interpolation factor, where zero gives this rotation and one gives the other rotation.
+    ///
+    /// Returns the interpolated rotation.
+    pub fn slerp(&self, other: &Self, t: f32) -> Self {
+        let mut other = *other;
+        let mut cos_theta = self.dot(&other);
+
+        // Both a quaternion and its negation represent the same rotation, so take the shorter arc
+        if cos_theta < 0.0 {
+            other = Self::new(-other.w, -other.x, -other.y, -other.z);
+            cos_theta = -cos_theta;
+        }
+
+        // Fall back to linear interpolation when the rotations are nearly identical
+        let (a, b) = if cos_theta > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let theta = cos_theta.acos();
+            let sin_theta = theta.sin();
+
+            (
+                ((1.0 - t) * theta).sin() / sin_theta,
+                (t * theta).sin() / sin_theta,
+            )
+        };
+
+        Self::new(
+            a * self.w + b * other.w,
+            a * self.x + b * other.x,
+            a * self.y + b * other.y,
+            a * self.z + b * other.z,
+        )
+        .normalize()
+    }
+}
+
+// Default to the identity rotation
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+// Multiply quaternions, composing rotations so that the right-hand rotation is applied first
+impl Mul for Quaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        )
+    }
+}
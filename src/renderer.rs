@@ -0,0 +1,820 @@
+use crate::camera::*;
+use crate::clip::*;
+use crate::color::*;
+use crate::depth_buffer::*;
+use crate::indexed_mesh::*;
+use crate::lighting::*;
+use crate::mesh::*;
+use crate::screen::*;
+use crate::transform::*;
+use crate::triangle::*;
+use crate::vector::*;
+
+/// Evaluate the edge function of a line for a point. The result is twice the signed area of the triangle formed by the three points, and its sign indicates which side of the line the point lies on.
+///
+/// `a`: the start of the line.
+/// `b`: the end of the line.
+/// `p`: the point to test.
+///
+/// Returns the value of the edge function.
+fn edge_function(a: &Vector<2>, b: &Vector<2>, p: &Vector<2>) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+/// Check whether an edge of a positively wound triangle is a top or left edge, for use with the top-left fill rule.
+///
+/// `a`: the start of the edge.
+/// `b`: the end of the edge.
+///
+/// Returns whether the edge is a top or left edge.
+fn is_top_left_edge(a: &Vector<2>, b: &Vector<2>) -> bool {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+
+    (dy == 0.0 && dx > 0.0) || dy < 0.0
+}
+
+/// A vector of a projected triangle, along with the attributes interpolated across the screen.
+#[derive(Clone, Copy, Debug)]
+struct ScreenVertex {
+    /// The position on the screen.
+    position: Vector<2>,
+    /// The projected depth.
+    depth: f32,
+    /// The luminance.
+    lum: f32,
+    /// The position in view space, if it was carried through projection.
+    view: Option<Vector<3>>,
+    /// The surface normal in view space, if the triangle has normals.
+    normal: Option<Vector<3>>,
+    /// The colored light received, if the triangle has been lit by colored lights.
+    illumination: Option<Vector<3>>,
+}
+
+// Screen vertex implementation
+impl ScreenVertex {
+    /// Split a projected triangle into its vertices.
+    ///
+    /// `triangle`: the projected triangle.
+    ///
+    /// Returns the vertices of the triangle.
+    fn from_triangle(triangle: &Triangle<2>) -> [Self; 3] {
+        let depths = triangle.get_depths();
+        let lums = triangle.get_luminances();
+        let views = triangle.get_view_positions();
+        let normals = triangle.get_normals();
+        let illumination = triangle.get_illumination();
+
+        [0, 1, 2].map(|i| Self {
+            position: triangle[i],
+            depth: depths[i],
+            lum: lums[i],
+            view: views.map(|views| views[i]),
+            normal: normals.map(|normals| normals[i]),
+            illumination: illumination.map(|illumination| illumination[i]),
+        })
+    }
+
+    /// Join three vertices back into a projected triangle.
+    ///
+    /// `vertices`: the vertices of the triangle.
+    ///
+    /// Returns the projected triangle.
+    fn to_triangle(vertices: [Self; 3]) -> Triangle<2> {
+        let mut triangle = Triangle::new(vertices.map(|vertex| vertex.position), 1.0);
+        triangle.set_depths(vertices.map(|vertex| vertex.depth));
+        triangle.set_luminances(vertices.map(|vertex| vertex.lum));
+        triangle.set_view_positions(
+            vertices
+                .iter()
+                .all(|vertex| vertex.view.is_some())
+                .then(|| vertices.map(|vertex| vertex.view.unwrap())),
+        );
+        triangle.set_normals(
+            vertices
+                .iter()
+                .all(|vertex| vertex.normal.is_some())
+                .then(|| vertices.map(|vertex| vertex.normal.unwrap())),
+        );
+        triangle.set_illumination(
+            vertices
+                .iter()
+                .all(|vertex| vertex.illumination.is_some())
+                .then(|| vertices.map(|vertex| vertex.illumination.unwrap())),
+        );
+
+        triangle
+    }
+
+    /// Correct screen space interpolation weights for perspective, so that view space attributes are interpolated linearly across the original surface rather than across the screen. The view space depth of each vertex is its w component in clip space.
+    ///
+    /// `vertices`: the vertices being interpolated.
+    /// `weights`: the screen space weight of each vertex, summing to one.
+    ///
+    /// Returns the corrected weights, which are unchanged if any vertex lacks a view space position.
+    fn perspective_weights<const V: usize>(vertices: [&Self; V], weights: [f32; V]) -> [f32; V] {
+        if vertices.iter().any(|vertex| vertex.view.is_none()) {
+            return weights;
+        }
+
+        let scaled: [f32; V] = std::array::from_fn(|i| weights[i] / vertices[i].view.unwrap()[2]);
+        let total: f32 = scaled.iter().sum();
+
+        scaled.map(|weight| weight / total)
+    }
+
+    /// Interpolate between two vertices by a screen space factor.
+    ///
+    /// `other`: the vertex to interpolate towards.
+    /// `t`: the interpolation factor in the range [0, 1].
+    /// `perspective`: whether view space attributes are corrected for perspective.
+    ///
+    /// Returns the interpolated vertex.
+    fn lerp(&self, other: &Self, t: f32, perspective: bool) -> Self {
+        let view_t = if perspective {
+            Self::perspective_weights([self, other], [1.0 - t, t])[1]
+        } else {
+            t
+        };
+
+        Self {
+            position: self.position.lerp(&other.position, t),
+            depth: self.depth + (other.depth - self.depth) * t,
+            lum: self.lum + (other.lum - self.lum) * t,
+            view: self.view.zip(other.view).map(|(a, b)| a.lerp(&b, view_t)),
+            normal: self
+                .normal
+                .zip(other.normal)
+                .map(|(a, b)| a.lerp(&b, view_t)),
+            illumination: self
+                .illumination
+                .zip(other.illumination)
+                .map(|(a, b)| a.lerp(&b, t)),
+        }
+    }
+}
+
+/// Renderer initialization options.
+pub struct RendererOptions {
+    /// The width of the screen in pixels.
+    pub width: usize,
+    /// The height of the screen in pixels.
+    pub height: usize,
+    /// The view near factor.
+    pub near: f32,
+    /// The view far factor.
+    pub far: f32,
+    /// The field of view in degrees.
+    pub fov: f32,
+    /// The kind of projection used to view the scene.
+    pub projection: Projection,
+    /// The intensity of low luminance values.
+    pub shadow_intensity: f32,
+    /// The comparison function used for depth testing.
+    pub depth_compare: DepthCompare,
+    /// Whether passing fragments write their depth to the depth buffer.
+    pub depth_write: bool,
+    /// The way filled triangles are lit.
+    pub shading: Shading,
+}
+
+// Support default values for renderer options
+impl Default for RendererOptions {
+    fn default() -> Self {
+        Self {
+            width: 800,
+            height: 600,
+            near: 0.1,
+            far: 1000.0,
+            fov: 90.0,
+            projection: Projection::Perspective,
+            shadow_intensity: 1.0,
+            depth_compare: DepthCompare::Always,
+            depth_write: true,
+            shading: Shading::Luminance,
+        }
+    }
+}
+
+/// Offscreen renderer abstraction, drawing into a screen buffer without the need for a window.
+#[derive(Clone, Debug)]
+pub struct Renderer {
+    /// The width of the screen in pixels.
+    width: usize,
+    /// The height of the screen in pixels.
+    height: usize,
+    /// The camera the scene is viewed through.
+    camera: Camera,
+    /// The intensity of low luminance values.
+    shadow_intensity: f32,
+    /// The comparison function used for depth testing.
+    depth_compare: DepthCompare,
+    /// Whether passing fragments write their depth to the depth buffer.
+    depth_write: bool,
+    /// The way filled triangles are lit.
+    shading: Shading,
+    /// The screen buffer.
+    buffer: Screen,
+    /// The depth buffer.
+    depth_buffer: DepthBuffer,
+}
+
+// Renderer implementation
+impl Renderer {
+    /// Create a new renderer.
+    ///
+    /// `options`: the renderer options.
+    ///
+    /// Returns the new renderer.
+    pub fn new(options: RendererOptions) -> Self {
+        let mut camera = Camera::new([0.0, 0.0, 0.0], options.fov, options.near, options.far);
+        camera.set_projection(options.projection);
+
+        Self {
+            width: options.width,
+            height: options.height,
+            camera,
+            shadow_intensity: options.shadow_intensity,
+            depth_compare: options.depth_compare,
+            depth_write: options.depth_write,
+            shading: options.shading,
+            buffer: Screen::new(options.width, options.height),
+            depth_buffer: DepthBuffer::new(options.width, options.height),
+        }
+    }
+
+    /// Get the screen buffer the renderer draws into.
+    ///
+    /// Returns a reference to the screen buffer.
+    pub fn screen(&self) -> &Screen {
+        &self.buffer
+    }
+
+    /// Get the screen buffer the renderer draws into, mutably.
+    ///
+    /// Returns a mutable reference to the screen buffer.
+    pub fn screen_mut(&mut self) -> &mut Screen {
+        &mut self.buffer
+    }
+
+    /// Get the depth buffer the renderer tests against.
+    ///
+    /// Returns a reference to the depth buffer.
+    pub fn depth_buffer(&self) -> &DepthBuffer {
+        &self.depth_buffer
+    }
+
+    /// Get the camera the scene is viewed through.
+    ///
+    /// Returns a reference to the camera.
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    /// Get the camera the scene is viewed through, mutably.
+    ///
+    /// Returns a mutable reference to the camera.
+    pub fn camera_mut(&mut self) -> &mut Camera {
+        &mut self.camera
+    }
+
+    /// Set the camera the scene is viewed through.
+    ///
+    /// `camera`: the new camera.
+    pub fn set_camera(&mut self, camera: Camera) {
+        self.camera = camera;
+    }
+
+    /// Get the width of the screen.
+    ///
+    /// Returns the width of the screen in pixels.
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Set the width of the screen.
+    ///
+    /// `width`: the new screen width in pixels.
+    pub fn set_width(&mut self, width: usize) {
+        self.width = width;
+        self.update_view();
+    }
+
+    /// Get the height of the screen.
+    ///
+    /// Returns the height of the screen in pixels.
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    /// Set the height of the screen.
+    ///
+    /// `height`: the new screen height in pixels.
+    pub fn set_height(&mut self, height: usize) {
+        self.height = height;
+        self.update_view();
+    }
+
+    /// Get the width and height of the screen.
+    ///
+    /// Returns a tuple of the width and height of the screen in pixels.
+    pub fn get_size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Set the width and height of the screen.
+    ///
+    /// `width`: the new screen width in pixels.
+    /// `height`: the new screen height in pixels.
+    pub fn set_size(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        self.update_view();
+    }
+
+    /// Get the view near factor.
+    ///
+    /// Returns the near factor.
+    pub fn get_near(&self) -> f32 {
+        self.camera.get_near()
+    }
+
+    /// Set the view near factor.
+    ///
+    /// `near`: the new near factor.
+    pub fn set_near(&mut self, near: f32) {
+        self.camera.set_near(near);
+    }
+
+    /// Get the view far factor.
+    ///
+    /// Returns the far factor.
+    pub fn get_far(&self) -> f32 {
+        self.camera.get_far()
+    }
+
+    /// Set the view far factor.
+    ///
+    /// `far`: the new far factor.
+    pub fn set_far(&mut self, far: f32) {
+        self.camera.set_far(far);
+    }
+
+    /// Get the field of view.
+    ///
+    /// Returns the field of view in degrees.
+    pub fn get_fov(&self) -> f32 {
+        self.camera.get_fov()
+    }
+
+    /// Set the field of view.
+    ///
+    /// `fov`: the new field of view in degrees.
+    pub fn set_fov(&mut self, fov: f32) {
+        self.camera.set_fov(fov);
+    }
+
+    /// Get the kind of projection used to view the scene.
+    ///
+    /// Returns the camera's projection.
+    pub fn get_projection(&self) -> Projection {
+        self.camera.get_projection()
+    }
+
+    /// Set the kind of projection used to view the scene.
+    ///
+    /// `projection`: the new projection.
+    pub fn set_projection(&mut self, projection: Projection) {
+        self.camera.set_projection(projection);
+    }
+
+    /// Get the shadow intensity.
+    ///
+    /// Returns the intensity of low luminance values.
+    pub fn get_shadow_intensity(&self) -> f32 {
+        self.shadow_intensity
+    }
+
+    /// Set the shadow intensity.
+    ///
+    /// `shadow_intensity`: the new intensity of low luminance values.
+    pub fn set_shadow_intensity(&mut self, shadow_intensity: f32) {
+        self.shadow_intensity = shadow_intensity;
+    }
+
+    /// Get the depth comparison function.
+    ///
+    /// Returns the comparison function used for depth testing.
+    pub fn get_depth_compare(&self) -> DepthCompare {
+        self.depth_compare
+    }
+
+    /// Set the depth comparison function.
+    ///
+    /// `depth_compare`: the new comparison function used for depth testing.
+    pub fn set_depth_compare(&mut self, depth_compare: DepthCompare) {
+        self.depth_compare = depth_compare;
+    }
+
+    /// Get whether depth writes are enabled.
+    ///
+    /// Returns whether passing fragments write their depth to the depth buffer.
+    pub fn get_depth_write(&self) -> bool {
+        self.depth_write
+    }
+
+    /// Set whether depth writes are enabled.
+    ///
+    /// `depth_write`: whether passing fragments should write their depth to the depth buffer.
+    pub fn set_depth_write(&mut self, depth_write: bool) {
+        self.depth_write = depth_write;
+    }
+
+    /// Get the way filled triangles are lit.
+    ///
+    /// Returns the shading mode.
+    pub fn get_shading(&self) -> &Shading {
+        &self.shading
+    }
+
+    /// Set the way filled triangles are lit.
+    ///
+    /// `shading`: the shading mode.
+    pub fn set_shading(&mut self, shading: Shading) {
+        self.shading = shading;
+    }
+
+    /// Get the aspect ratio of the screen.
+    ///
+    /// Returns the screen's height divided by its width.
+    pub fn aspect_ratio(&self) -> f32 {
+        (self.height as f32) / (self.width as f32)
+    }
+
+    /// Update the screen and depth buffers. This should be called after any update to screen size.
+    fn update_view(&mut self) {
+        self.buffer = Screen::new(self.width, self.height);
+        self.depth_buffer = DepthBuffer::new(self.width, self.height);
+    }
+
+    /// Apply a luminance to a color.
+    ///
+    /// `color`: the color.
+    /// `luminance`: the luminance value.
+    ///
+    /// Returns the resulting color with luminance applied.
+    fn apply_color_luminance(&self, color: Color, luminance: f32) -> Color {
+        let lum = 1.0 - ((1.0 - luminance) * self.shadow_intensity);
+
+        Color::new(
+            ((color[0] as f32) * lum).round() as u8,
+            ((color[1] as f32) * lum).round() as u8,
+            ((color[2] as f32) * lum).round() as u8,
+        )
+    }
+
+    /// Apply colored light to a color.
+    ///
+    /// `color`: the color.
+    /// `illumination`: the red, green and blue light, where one leaves a color unchanged.
+    ///
+    /// Returns the resulting color with the light applied.
+    fn apply_color_illumination(&self, color: Color, illumination: &Vector<3>) -> Color {
+        let channel = |i: usize| {
+            ((color[i] as f32) * illumination[i])
+                .round()
+                .clamp(0.0, 255.0) as u8
+        };
+
+        Color::new(channel(0), channel(1), channel(2))
+    }
+
+    /// Fill the screen with a single color.
+    ///
+    /// `color`: the color with which to fill the screen.
+    pub fn fill<C: Into<Color>>(&mut self, color: C) {
+        *self.buffer = vec![color.into().into(); self.width * self.height];
+    }
+
+    /// Clear the depth buffer to the farthest possible depth for the current depth comparison, so that the first fragment drawn to each pixel passes a `Less` or `Greater` style comparison. This should usually be called once per frame, alongside `fill`.
+    pub fn clear_depth(&mut self) {
+        self.depth_buffer.clear(self.depth_compare.clear_value());
+    }
+
+    /// Draw a pixel to the screen if it passes the depth test.
+    ///
+    /// `x`: the x coordinate of the pixel.
+    /// `y`: the y coordinate of the pixel.
+    /// `depth`: the depth of the pixel.
+    /// `color`: the color of the pixel.
+    fn draw_depth(&mut self, x: usize, y: usize, depth: f32, color: Color) {
+        if let Some(stored) = self.depth_buffer.get_mut(x, y) {
+            if !self.depth_compare.test(depth, *stored) {
+                return;
+            }
+
+            if self.depth_write {
+                *stored = depth;
+            }
+
+            self.buffer[(x, y)] = color.into();
+        }
+    }
+
+    /// Draw a pixel to the screen.
+    ///
+    /// `p`: the point on the screen to draw.
+    /// `color`: the color of the point.
+    pub fn draw<V: Into<Vector<2>>, C: Into<Color>>(&mut self, p: V, color: C) {
+        let p: Vector<2> = p.into();
+        let color: Color = color.into();
+
+        let px = p[0].round() as usize;
+        let py = p[1].round() as usize;
+
+        if let Some(point) = self.buffer.get_mut(px, py) {
+            *point = color.into();
+        }
+    }
+
+    /// Draw a line to the screen.
+    ///
+    /// `p1`: one endpoint of the line.
+    /// `p2`: the other endpoint of the line.
+    /// `color`: the color of the line.
+    pub fn draw_line<V: Into<Vector<2>>, C: Into<Color> + Copy>(&mut self, p1: V, p2: V, color: C) {
+        let p1: Vector<2> = p1.into();
+        let p2: Vector<2> = p2.into();
+
+        let p1x = p1[0];
+        let p1y = p1[1];
+        let p2x = p2[0];
+        let p2y = p2[1];
+        let dx = p1x - p2x;
+        let dy = p1y - p2y;
+        let steps = dx.abs().max(dy.abs()).round() as usize;
+
+        for i in 0..=steps {
+            let steps_progress = (i as f32) / (steps as f32);
+            let x = p1x + ((p2x - p1x) * steps_progress);
+            let y = p1y + ((p2y - p1y) * steps_progress);
+
+            self.draw([x, y], color);
+        }
+    }
+
+    /// Draw a shape to the screen. This will panic if called with less than two points.
+    ///
+    /// `points`: a list of points to draw.
+    /// `color`: the color of the shape.
+    pub fn draw_shape<V: Into<Vector<2>> + Clone, C: Into<Color> + Copy>(
+        &mut self,
+        points: Vec<V>,
+        color: C,
+    ) {
+        if points.len() < 2 {
+            panic!("expected at least two points");
+        }
+
+        for i in 0..points.len() - 1 {
+            self.draw_line(points[i].clone(), points[i + 1].clone(), color);
+        }
+
+        self.draw_line(
+            points.first().unwrap().clone(),
+            points.last().unwrap().clone(),
+            color,
+        );
+    }
+
+    /// Draw a mesh to the screen.
+    ///
+    /// `mesh`: the mesh to draw.
+    /// `color`: the color of the mesh.
+    pub fn draw_mesh<C: Into<Color> + Copy>(&mut self, mesh: &Mesh<2>, color: C) {
+        for triangle in mesh.triangles() {
+            self.draw_shape(triangle.vectors().to_vec(), color);
+        }
+    }
+
+    /// Clip a projected triangle against the edges of the screen.
+    ///
+    /// `triangle`: the triangle to clip.
+    ///
+    /// Returns the triangles covering the visible part of the original triangle, which will be empty if the triangle lies entirely offscreen.
+    fn clip_to_screen(&self, triangle: &Triangle<2>) -> Vec<Triangle<2>> {
+        let max_x = self.width as f32;
+        let max_y = self.height as f32;
+        let perspective = self.camera.get_projection() == Projection::Perspective;
+
+        // Each vertex carries its attributes so they can be interpolated at the new edges
+        let mut polygon = ScreenVertex::from_triangle(triangle).to_vec();
+
+        // Clip against the left, right, top and bottom edges in turn
+        for (axis, sign, bound) in [
+            (0, 1.0, 0.0),
+            (0, -1.0, max_x),
+            (1, 1.0, 0.0),
+            (1, -1.0, max_y),
+        ] {
+            polygon = clip_polygon(
+                &polygon,
+                |vertex| sign * (vertex.position[axis] - bound),
+                |a, b, t| a.lerp(b, t, perspective),
+            );
+        }
+
+        triangulate_fan(&polygon)
+            .into_iter()
+            .map(ScreenVertex::to_triangle)
+            .collect()
+    }
+
+    /// Fill in a triangle on the screen. Triangles are clipped against the edges of the screen before being drawn, and lit according to the renderer's shading mode.
+    ///
+    /// `triangle`: the triangle to fill.
+    /// `color`: the color of the triangle.
+    pub fn fill_triangle<C: Into<Color> + Copy>(&mut self, triangle: &Triangle<2>, color: C) {
+        let color = color.into();
+
+        // The shading is moved out while rasterizing, so its lights can be borrowed while pixels are drawn
+        let shading = std::mem::take(&mut self.shading);
+
+        for clipped in self.clip_to_screen(triangle) {
+            self.rasterize_triangle(&clipped, color, &shading);
+        }
+
+        self.shading = shading;
+    }
+
+    /// Rasterize a triangle lying entirely on the screen. Pixels are sampled at their centers, and a top-left fill rule ensures that pixels on an edge shared by two triangles are drawn exactly once. Degenerate triangles are skipped.
+    ///
+    /// `triangle`: the triangle to rasterize.
+    /// `color`: the color of the triangle, before lighting is applied.
+    /// `shading`: the way the triangle is lit.
+    fn rasterize_triangle(&mut self, triangle: &Triangle<2>, color: Color, shading: &Shading) {
+        let mut vertices = ScreenVertex::from_triangle(triangle);
+        let [v0, v1, v2] = vertices.map(|vertex| vertex.position);
+        let mut area = edge_function(&v0, &v1, &v2);
+
+        if area == 0.0 || !area.is_finite() {
+            return;
+        }
+
+        // Ensure a consistent winding so every edge function is positive inside the triangle
+        if area < 0.0 {
+            vertices.swap(0, 2);
+            area = -area;
+        }
+
+        let [v0, v1, v2] = vertices.map(|vertex| vertex.position);
+        let edges = [(v1, v2), (v2, v0), (v0, v1)];
+        let top_left = edges.map(|(a, b)| is_top_left_edge(&a, &b));
+
+        let min_x = v0[0].min(v1[0]).min(v2[0]);
+        let max_x = v0[0].max(v1[0]).max(v2[0]);
+        let min_y = v0[1].min(v1[1]).min(v2[1]);
+        let max_y = v0[1].max(v1[1]).max(v2[1]);
+
+        // Only pixels whose centers lie within the bounding box can be covered
+        let x_start = (min_x - 0.5).ceil().max(0.0) as usize;
+        let x_end = ((max_x - 0.5).floor() as isize).min(self.width as isize - 1);
+        let y_start = (min_y - 0.5).ceil().max(0.0) as usize;
+        let y_end = ((max_y - 0.5).floor() as isize).min(self.height as isize - 1);
+
+        if x_end < 0 || y_end < 0 {
+            return;
+        }
+
+        let perspective = self.camera.get_projection() == Projection::Perspective;
+        let lums = vertices.map(|vertex| vertex.lum);
+        let illumination = vertices
+            .iter()
+            .all(|vertex| vertex.illumination.is_some())
+            .then(|| vertices.map(|vertex| vertex.illumination.unwrap()));
+
+        // Per-pixel lighting needs the view space positions carried through projection
+        let per_pixel = match shading {
+            Shading::PerPixel {
+                lights,
+                material,
+                model,
+            } if vertices.iter().all(|vertex| vertex.view.is_some()) => {
+                let [p0, p1, p2] = vertices.map(|vertex| vertex.view.unwrap());
+                let face_normal = (p1 - p0).cross(&(p2 - p0)).normalize();
+
+                // Triangles without normals are lit on whichever side faces the viewer
+                let face_normal = match perspective {
+                    true if face_normal.dot(&p0) > 0.0 => -face_normal,
+                    false if face_normal[2] > 0.0 => -face_normal,
+                    _ => face_normal,
+                };
+
+                Some((lights, *material, *model, face_normal))
+            }
+            _ => None,
+        };
+
+        // Flat shaded triangles only need their color lit once
+        let flat_color = match (&per_pixel, illumination) {
+            (Some(_), _) => None,
+            (None, Some([i0, i1, i2])) => {
+                (i0 == i1 && i1 == i2).then(|| self.apply_color_illumination(color, &i0))
+            }
+            (None, None) => (lums[0] == lums[1] && lums[1] == lums[2])
+                .then(|| self.apply_color_luminance(color, lums[0])),
+        };
+
+        for py in y_start..=(y_end as usize) {
+            for px in x_start..=(x_end as usize) {
+                let p = Vector::from([px as f32 + 0.5, py as f32 + 0.5]);
+                let weights = [0, 1, 2].map(|i| edge_function(&edges[i].0, &edges[i].1, &p));
+
+                let covered =
+                    (0..3).all(|i| weights[i] > 0.0 || (weights[i] == 0.0 && top_left[i]));
+
+                if !covered {
+                    continue;
+                }
+
+                let weights = weights.map(|weight| weight / area);
+                let depth = (0..3).map(|i| weights[i] * vertices[i].depth).sum();
+
+                let color_lit = match (&per_pixel, flat_color) {
+                    (Some((lights, material, model, face_normal)), _) => {
+                        let weights = if perspective {
+                            ScreenVertex::perspective_weights(vertices.each_ref(), weights)
+                        } else {
+                            weights
+                        };
+
+                        let interpolate = |attribute: [Vector<3>; 3]| {
+                            attribute[0] * weights[0]
+                                + attribute[1] * weights[1]
+                                + attribute[2] * weights[2]
+                        };
+
+                        let position = interpolate(vertices.map(|vertex| vertex.view.unwrap()));
+                        let normal = match vertices[0].normal {
+                            Some(_) => interpolate(vertices.map(|vertex| vertex.normal.unwrap()))
+                                .normalize(),
+                            None => *face_normal,
+                        };
+                        let view = if perspective {
+                            -position.normalize()
+                        } else {
+                            Vector::from([0.0, 0.0, -1.0])
+                        };
+
+                        material.shade_lights(color, &position, &normal, &view, lights, *model)
+                    }
+                    (None, Some(flat_color)) => flat_color,
+                    (None, None) => match illumination {
+                        Some(illumination) => {
+                            let light = illumination[0] * weights[0]
+                                + illumination[1] * weights[1]
+                                + illumination[2] * weights[2];
+                            self.apply_color_illumination(color, &light)
+                        }
+                        None => {
+                            let lum = (0..3).map(|i| weights[i] * lums[i]).sum();
+                            self.apply_color_luminance(color, lum)
+                        }
+                    },
+                };
+
+                self.draw_depth(px, py, depth, color_lit);
+            }
+        }
+    }
+
+    /// Fill in a mesh on the screen.
+    ///
+    /// `mesh`: the mesh to fill.
+    /// `color`: the color of the mesh.
+    pub fn fill_mesh<C: Into<Color> + Copy>(&mut self, mesh: &Mesh<2>, color: C) {
+        for triangle in mesh.triangles() {
+            self.fill_triangle(triangle, color);
+        }
+    }
+
+    /// Generate a transformation for a mesh based on the renderer's camera and screen size.
+    ///
+    /// `mesh`: the mesh to transform.
+    ///
+    /// Returns a transformation for the mesh.
+    pub fn mesh_transformation<T: Clone>(&self, mesh: &Mesh<3, T>) -> Transform<3, T> {
+        Transform::new(mesh, &self.camera, self.aspect_ratio())
+    }
+
+    /// Generate a transformation for an indexed mesh based on the renderer's camera and screen size. Each shared vertex is only transformed once.
+    ///
+    /// `mesh`: the indexed mesh to transform.
+    ///
+    /// Returns a transformation for the mesh.
+    pub fn indexed_mesh_transformation<T: Clone>(
+        &self,
+        mesh: &IndexedMesh<3, T>,
+    ) -> Transform<3, T> {
+        Transform::new_indexed(mesh, &self.camera, self.aspect_ratio())
+    }
+}
@@ -27,6 +27,20 @@ impl Screen {
         }
     }
 
+    /// Get the width of the screen buffer.
+    ///
+    /// Returns the width of the screen in pixels.
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height of the screen buffer.
+    ///
+    /// Returns the height of the screen in pixels.
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
     /// Get a reference to a pixel in the screen buffer.
     ///
     /// `x`: the x coordinate of the pixel.
@@ -61,7 +75,7 @@ impl Index<(usize, usize)> for Screen {
     type Output = u32;
 
     fn index(&self, index: (usize, usize)) -> &Self::Output {
-        &self.get(index.0, index.1).unwrap()
+        self.get(index.0, index.1).unwrap()
     }
 }
 
@@ -0,0 +1,450 @@
+use crate::mesh::*;
+use crate::triangle::*;
+use crate::vector::*;
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+
+/// The size of a binary STL header in bytes, including the triangle count.
+const BINARY_HEADER_SIZE: usize = 84;
+
+/// The size of a single binary STL facet record in bytes.
+const BINARY_FACET_SIZE: usize = 50;
+
+/// The header text written to binary STL files.
+const BINARY_HEADER_TEXT: &[u8] = b"binary STL written by render-3d";
+
+/// The name of the solid written to ASCII STL files.
+const ASCII_SOLID_NAME: &str = "mesh";
+
+/// An error type for reading STL files.
+#[derive(Debug)]
+pub enum StlError {
+    /// An error reading the file.
+    IOError(io::Error),
+    /// A malformed statement in an ASCII STL file.
+    ParseError {
+        /// The line number of the statement, starting from one.
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+    /// A binary STL file whose size does not match its declared triangle count.
+    SizeMismatch {
+        /// The number of triangles declared in the header.
+        triangles: u32,
+        /// The file size in bytes implied by the triangle count.
+        expected: usize,
+        /// The actual file size in bytes.
+        actual: usize,
+    },
+}
+
+// Convert I/O errors to STL errors
+impl From<io::Error> for StlError {
+    fn from(e: io::Error) -> Self {
+        Self::IOError(e)
+    }
+}
+
+// Display STL errors
+impl fmt::Display for StlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IOError(e) => write!(f, "{}", e),
+            Self::ParseError { line, message } => write!(f, "line {}: {}", line, message),
+            Self::SizeMismatch {
+                triangles,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "binary STL declares {} triangles, requiring {} bytes, but has {} bytes",
+                triangles, expected, actual
+            ),
+        }
+    }
+}
+
+// Mark STL error as an implementation of the standard error trait
+impl error::Error for StlError {}
+
+/// A result type for reading STL files.
+pub type StlResult<T> = Result<T, StlError>;
+
+/// A variant of the STL file format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StlFormat {
+    /// Human readable text.
+    Ascii,
+    /// Little endian binary records.
+    Binary,
+}
+
+// STL format implementation
+impl StlFormat {
+    /// Detect the variant of an STL file from its contents. Binary files may also begin with `solid`, so a file is only treated as ASCII if it is entirely ASCII text and its size does not match a binary file's declared triangle count.
+    ///
+    /// `data`: the contents of the file.
+    ///
+    /// Returns the detected format.
+    pub fn detect(data: &[u8]) -> Self {
+        if let Some(count) = binary_triangle_count(data) {
+            if binary_file_size(count) == data.len() {
+                return Self::Binary;
+            }
+        }
+
+        let start = data.iter().position(|byte| !byte.is_ascii_whitespace());
+
+        match start {
+            Some(start) if data[start..].starts_with(b"solid") && data.is_ascii() => Self::Ascii,
+            _ => Self::Binary,
+        }
+    }
+}
+
+/// Read the triangle count from a binary STL header.
+///
+/// `data`: the contents of the file.
+///
+/// Returns an option containing the triangle count, or the none variant if the file is too short to contain a header.
+fn binary_triangle_count(data: &[u8]) -> Option<u32> {
+    let bytes = data.get(BINARY_HEADER_SIZE - 4..BINARY_HEADER_SIZE)?;
+
+    Some(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// Calculate the size of a binary STL file.
+///
+/// `count`: the number of triangles in the file.
+///
+/// Returns the file size in bytes.
+fn binary_file_size(count: u32) -> usize {
+    BINARY_HEADER_SIZE + count as usize * BINARY_FACET_SIZE
+}
+
+/// Build a triangle from STL facet data, using the stored normal to correct the winding order. The stored normal is ignored if it is zero.
+///
+/// `normal`: the stored facet normal.
+/// `vertices`: the facet vertices.
+///
+/// Returns the triangle, wound counter-clockwise about the stored normal.
+fn facet_triangle(normal: [f32; 3], vertices: [[f32; 3]; 3]) -> Triangle<3, f32> {
+    let [a, mut b, mut c] = vertices.map(Vector::from);
+    let normal = Vector::from(normal);
+
+    if normal.length_squared() > 0.0 && (b - a).cross(&(c - a)).dot(&normal) < 0.0 {
+        (b, c) = (c, b);
+    }
+
+    Triangle::new([a, b, c], 1.0)
+}
+
+/// Calculate the unit normal of a triangle for writing to an STL file.
+///
+/// `triangle`: the triangle.
+///
+/// Returns the unit normal, or the zero vector if the triangle is degenerate.
+fn facet_normal(triangle: &Triangle<3, f32>) -> [f32; 3] {
+    let [a, b, c] = *triangle.vectors();
+    let normal = (b - a).cross(&(c - a));
+
+    if normal.length_squared() > 0.0 {
+        *normal.normalize().coords()
+    } else {
+        [0.0; 3]
+    }
+}
+
+/// An iterator over the whitespace separated tokens of an ASCII STL file, along with their line numbers.
+struct Tokens<'a> {
+    /// The remaining lines of the file, along with their line numbers.
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    /// The remaining tokens on the current line.
+    current: std::str::SplitWhitespace<'a>,
+    /// The current line number, starting from one.
+    line: usize,
+}
+
+// Token iterator implementation
+impl<'a> Tokens<'a> {
+    /// Create a new token iterator.
+    ///
+    /// `text`: the contents of the file.
+    ///
+    /// Returns the new iterator.
+    fn new(text: &'a str) -> Self {
+        Self {
+            lines: text.lines().enumerate(),
+            current: "".split_whitespace(),
+            line: 1,
+        }
+    }
+
+    /// Skip the remaining tokens on the current line.
+    fn skip_line(&mut self) {
+        self.current = "".split_whitespace();
+    }
+
+    /// Get the next token, which must be present.
+    ///
+    /// `what`: a description of the expected token, used in error messages.
+    ///
+    /// Returns a result containing the token, or the error variant if the file ended.
+    fn expect_any(&mut self, what: &str) -> StlResult<&'a str> {
+        self.next().ok_or_else(|| StlError::ParseError {
+            line: self.line,
+            message: format!("expected {}, found end of file", what),
+        })
+    }
+
+    /// Get the next token, which must match a keyword.
+    ///
+    /// `keyword`: the expected keyword.
+    ///
+    /// Returns a result of the match, or the error variant if a different token was found.
+    fn expect(&mut self, keyword: &str) -> StlResult<()> {
+        let token = self.expect_any(&format!("'{}'", keyword))?;
+
+        if token != keyword {
+            return Err(StlError::ParseError {
+                line: self.line,
+                message: format!("expected '{}', found '{}'", keyword, token),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Parse the next three tokens as floating point values.
+    ///
+    /// `what`: a description of the values, used in error messages.
+    ///
+    /// Returns a result containing the three values.
+    fn expect_triple(&mut self, what: &str) -> StlResult<[f32; 3]> {
+        let mut values = [0.0; 3];
+
+        for value in values.iter_mut() {
+            let token = self.expect_any(what)?;
+
+            *value = token.parse().map_err(|_| StlError::ParseError {
+                line: self.line,
+                message: format!("invalid {} '{}'", what, token),
+            })?;
+        }
+
+        Ok(values)
+    }
+}
+
+// Iterate over tokens
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.current.next() {
+                return Some(token);
+            }
+
+            let (index, line) = self.lines.next()?;
+            self.line = index + 1;
+            self.current = line.split_whitespace();
+        }
+    }
+}
+
+/// Parse the contents of an ASCII STL file.
+///
+/// `data`: the contents of the file.
+///
+/// Returns a result containing the triangles in the file.
+fn parse_ascii(data: &[u8]) -> StlResult<Vec<Triangle<3, f32>>> {
+    let text = std::str::from_utf8(data).map_err(|e| StlError::ParseError {
+        line: data[..e.valid_up_to()]
+            .iter()
+            .filter(|&&byte| byte == b'\n')
+            .count()
+            + 1,
+        message: "invalid text encoding".to_owned(),
+    })?;
+
+    let mut tokens = Tokens::new(text);
+    let mut triangles = Vec::new();
+
+    // The solid name runs to the end of the line and may contain spaces
+    tokens.expect("solid")?;
+    tokens.skip_line();
+
+    loop {
+        match tokens.expect_any("'facet' or 'endsolid'")? {
+            "facet" => {}
+            "endsolid" => break,
+            token => {
+                return Err(StlError::ParseError {
+                    line: tokens.line,
+                    message: format!("expected 'facet' or 'endsolid', found '{}'", token),
+                })
+            }
+        }
+
+        tokens.expect("normal")?;
+        let normal = tokens.expect_triple("normal")?;
+        tokens.expect("outer")?;
+        tokens.expect("loop")?;
+
+        let mut vertices = [[0.0; 3]; 3];
+
+        for vertex in vertices.iter_mut() {
+            tokens.expect("vertex")?;
+            *vertex = tokens.expect_triple("vertex")?;
+        }
+
+        tokens.expect("endloop")?;
+        tokens.expect("endfacet")?;
+
+        triangles.push(facet_triangle(normal, vertices));
+    }
+
+    Ok(triangles)
+}
+
+/// Parse the contents of a binary STL file.
+///
+/// `data`: the contents of the file.
+///
+/// Returns a result containing the triangles in the file, or the error variant if the file size does not match the declared triangle count.
+fn parse_binary(data: &[u8]) -> StlResult<Vec<Triangle<3, f32>>> {
+    let count = binary_triangle_count(data).ok_or(StlError::SizeMismatch {
+        triangles: 0,
+        expected: BINARY_HEADER_SIZE,
+        actual: data.len(),
+    })?;
+
+    let expected = binary_file_size(count);
+
+    if expected != data.len() {
+        return Err(StlError::SizeMismatch {
+            triangles: count,
+            expected,
+            actual: data.len(),
+        });
+    }
+
+    let triangles = data[BINARY_HEADER_SIZE..]
+        .chunks_exact(BINARY_FACET_SIZE)
+        .map(|record| {
+            let value = |i: usize| {
+                let offset = i * 4;
+                f32::from_le_bytes(record[offset..offset + 4].try_into().unwrap())
+            };
+            let triple = |i: usize| [value(i), value(i + 1), value(i + 2)];
+
+            facet_triangle(triple(0), [triple(3), triple(6), triple(9)])
+        })
+        .collect();
+
+    Ok(triangles)
+}
+
+// STL encoding and decoding for meshes
+impl Mesh<3, f32> {
+    /// Read a mesh from an STL file, detecting whether it is ASCII or binary.
+    ///
+    /// `reader`: the reader from which the file will be read.
+    ///
+    /// Returns a result containing the mesh, or the error variant if the file could not be read or parsed.
+    pub fn read_stl<R: Read>(mut reader: R) -> StlResult<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let triangles = match StlFormat::detect(&data) {
+            StlFormat::Ascii => parse_ascii(&data)?,
+            StlFormat::Binary => parse_binary(&data)?,
+        };
+
+        Ok(Self::new(triangles))
+    }
+
+    /// Load a mesh from an STL file, detecting whether it is ASCII or binary.
+    ///
+    /// `path`: the path of the STL file.
+    ///
+    /// Returns a result containing the mesh, or the error variant if the file could not be read or parsed.
+    pub fn load_stl<P: AsRef<Path>>(path: P) -> StlResult<Self> {
+        Self::read_stl(File::open(path)?)
+    }
+
+    /// Write the mesh as an STL file. Facet normals are calculated from each triangle's winding order.
+    ///
+    /// `writer`: the writer to which the file will be written.
+    /// `format`: the STL variant to write.
+    ///
+    /// Returns a result of the write operation.
+    pub fn write_stl<W: Write>(&self, mut writer: W, format: StlFormat) -> io::Result<()> {
+        match format {
+            StlFormat::Ascii => {
+                writeln!(writer, "solid {}", ASCII_SOLID_NAME)?;
+
+                for triangle in self.triangles() {
+                    let [nx, ny, nz] = facet_normal(triangle);
+                    writeln!(writer, "  facet normal {:e} {:e} {:e}", nx, ny, nz)?;
+                    writeln!(writer, "    outer loop")?;
+
+                    for vector in triangle.vectors() {
+                        let [x, y, z] = *vector.coords();
+                        writeln!(writer, "      vertex {:e} {:e} {:e}", x, y, z)?;
+                    }
+
+                    writeln!(writer, "    endloop")?;
+                    writeln!(writer, "  endfacet")?;
+                }
+
+                writeln!(writer, "endsolid {}", ASCII_SOLID_NAME)?;
+            }
+            StlFormat::Binary => {
+                let count = u32::try_from(self.triangles().len()).map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "too many triangles for a binary STL file",
+                    )
+                })?;
+
+                let mut header = [0u8; BINARY_HEADER_SIZE - 4];
+                header[..BINARY_HEADER_TEXT.len()].copy_from_slice(BINARY_HEADER_TEXT);
+                writer.write_all(&header)?;
+                writer.write_all(&count.to_le_bytes())?;
+
+                for triangle in self.triangles() {
+                    let vectors = triangle.vectors().map(|vector| *vector.coords());
+
+                    for value in [facet_normal(triangle)]
+                        .iter()
+                        .chain(vectors.iter())
+                        .flatten()
+                    {
+                        writer.write_all(&value.to_le_bytes())?;
+                    }
+
+                    // The attribute byte count is unused
+                    writer.write_all(&[0, 0])?;
+                }
+            }
+        }
+
+        writer.flush()
+    }
+
+    /// Save the mesh to an STL file.
+    ///
+    /// `path`: the path of the STL file.
+    /// `format`: the STL variant to write.
+    ///
+    /// Returns a result of the save operation.
+    pub fn save_stl<P: AsRef<Path>>(&self, path: P, format: StlFormat) -> io::Result<()> {
+        self.write_stl(BufWriter::new(File::create(path)?), format)
+    }
+}
@@ -1,9 +1,12 @@
+use crate::camera::*;
+use crate::clip::*;
+use crate::indexed_mesh::*;
+use crate::lighting::*;
 use crate::matrix::*;
 use crate::mesh::*;
+use crate::quaternion::*;
 use crate::triangle::*;
 use crate::vector::*;
-use std::fmt;
-use std::ops::{Add, Mul};
 
 /// Generate an x rotation matrix.
 ///
@@ -53,13 +56,242 @@ fn generate_z_rotation_matrix(angle: f32) -> Matrix<4, 4, f32> {
     z_rotation_matrix
 }
 
+/// Generate a rotation matrix about an arbitrary axis using Rodrigues' rotation formula.
+///
+/// `axis`: the axis of rotation, which does not need to be normalized.
+/// `angle`: the angle of rotation in radians around the axis.
+///
+/// Returns the 4x4 axis rotation matrix.
+fn generate_axis_rotation_matrix(axis: &Vector<3, f32>, angle: f32) -> Matrix<4, 4, f32> {
+    let k = axis.normalize();
+    let (sin, cos) = angle.sin_cos();
+    let mut axis_rotation_matrix = Matrix::<4, 4, f32>::identity();
+
+    // The cross product matrix of the axis
+    let cross = [[0.0, -k[2], k[1]], [k[2], 0.0, -k[0]], [-k[1], k[0], 0.0]];
+
+    for row in 0..3 {
+        for col in 0..3 {
+            let identity = if row == col { 1.0 } else { 0.0 };
+
+            axis_rotation_matrix[(col, row)] =
+                identity * cos + cross[row][col] * sin + k[row] * k[col] * (1.0 - cos);
+        }
+    }
+
+    axis_rotation_matrix
+}
+
+/// A pending affine transformation in `N`-dimensions, made up of a linear part followed by an offset.
+#[derive(Clone, Debug)]
+struct Affine<const N: usize> {
+    /// The linear part of the transformation.
+    linear: Matrix<N, N, f32>,
+    /// The offset applied after the linear part.
+    offset: Vector<N, f32>,
+}
+
+// Affine transformation implementation
+impl<const N: usize> Affine<N> {
+    /// Create an identity affine transformation.
+    ///
+    /// Returns the new affine transformation.
+    fn identity() -> Self {
+        Self {
+            linear: Matrix::identity(),
+            offset: Vector::from([0.0; N]),
+        }
+    }
+
+    /// Compose another affine transformation after this one.
+    ///
+    /// `linear`: the linear part of the transformation to apply afterwards.
+    /// `offset`: the offset of the transformation to apply afterwards.
+    ///
+    /// Returns the composed affine transformation.
+    fn then(&self, linear: &Matrix<N, N, f32>, offset: &Vector<N, f32>) -> Self {
+        Self {
+            linear: *linear * self.linear,
+            offset: *linear * self.offset + *offset,
+        }
+    }
+
+    /// Apply the affine transformation to a vector.
+    ///
+    /// `vector`: the vector to transform.
+    ///
+    /// Returns the transformed vector.
+    fn apply(&self, vector: &Vector<N, f32>) -> Vector<N, f32> {
+        self.linear * *vector + self.offset
+    }
+
+    /// Calculate the matrix transforming surface normals from the linear part, so that normals stay perpendicular to the surface. Normals only exist in three dimensions.
+    ///
+    /// Returns an option containing the normal matrix, or the none variant if the transformation is not three dimensional.
+    fn normal_matrix(&self) -> Option<Matrix<3, 3, f32>> {
+        if N != 3 {
+            return None;
+        }
+
+        let mut linear = Matrix::<3, 3, f32>::new();
+
+        for x in 0..3 {
+            for y in 0..3 {
+                linear[(x, y)] = self.linear[(x, y)];
+            }
+        }
+
+        Some(linear.normal_matrix())
+    }
+}
+
+// Three dimensional affine transformations
+impl Affine<3> {
+    /// Create an affine transformation from a 4x4 matrix, discarding its bottom row.
+    ///
+    /// `matrix`: the 4x4 matrix.
+    ///
+    /// Returns the new affine transformation.
+    fn from_matrix(matrix: &Matrix<4, 4, f32>) -> Self {
+        let mut linear = Matrix::<3, 3, f32>::new();
+
+        for x in 0..3 {
+            for y in 0..3 {
+                linear[(x, y)] = matrix[(x, y)];
+            }
+        }
+
+        Self {
+            linear,
+            offset: Vector::from([matrix[(3, 0)], matrix[(3, 1)], matrix[(3, 2)]]),
+        }
+    }
+}
+
+/// The attributes carried by each of a triangle's vectors, which are interpolated when the triangle is clipped.
+#[derive(Clone, Copy, Debug)]
+struct VertexAttributes {
+    /// The luminance.
+    lum: f32,
+    /// The surface normal, if the triangle has normals.
+    normal: Option<Vector<3>>,
+    /// The colored light received, if the triangle has been lit by colored lights.
+    illumination: Option<Vector<3>>,
+}
+
+// Vertex attribute implementation
+impl VertexAttributes {
+    /// Get the attributes of each of a triangle's vectors.
+    ///
+    /// `triangle`: the triangle.
+    ///
+    /// Returns the attributes of each vector.
+    fn from_triangle<const N: usize>(triangle: &Triangle<N, f32>) -> [Self; 3] {
+        let lums = triangle.get_luminances();
+        let normals = triangle.get_normals();
+        let illumination = triangle.get_illumination();
+
+        [0, 1, 2].map(|i| Self {
+            lum: lums[i],
+            normal: normals.map(|normals| normals[i]),
+            illumination: illumination.map(|illumination| illumination[i]),
+        })
+    }
+
+    /// Set the attributes of each of a triangle's vectors.
+    ///
+    /// `attributes`: the attributes of each vector.
+    /// `triangle`: the triangle.
+    fn apply<const N: usize>(attributes: [Self; 3], triangle: &mut Triangle<N, f32>) {
+        triangle.set_luminances(attributes.map(|attribute| attribute.lum));
+        triangle.set_normals(
+            attributes
+                .iter()
+                .all(|attribute| attribute.normal.is_some())
+                .then(|| attributes.map(|attribute| attribute.normal.unwrap())),
+        );
+        triangle.set_illumination(
+            attributes
+                .iter()
+                .all(|attribute| attribute.illumination.is_some())
+                .then(|| attributes.map(|attribute| attribute.illumination.unwrap())),
+        );
+    }
+
+    /// Interpolate between the attributes of two vectors.
+    ///
+    /// `other`: the attributes to interpolate towards.
+    /// `t`: the interpolation factor in the range [0, 1].
+    ///
+    /// Returns the interpolated attributes.
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            lum: self.lum + (other.lum - self.lum) * t,
+            normal: self.normal.zip(other.normal).map(|(a, b)| a.lerp(&b, t)),
+            illumination: self
+                .illumination
+                .zip(other.illumination)
+                .map(|(a, b)| a.lerp(&b, t)),
+        }
+    }
+}
+
+/// A vector in homogeneous clip space, along with the attributes carried through projection.
+#[derive(Clone, Copy, Debug)]
+struct ClipVertex {
+    /// The position in clip space.
+    clip: Vector<4>,
+    /// The position in view space.
+    view: Vector<3>,
+    /// The attributes of the vector, with any normal in view space.
+    attributes: VertexAttributes,
+}
+
+// Clip space vertex implementation
+impl ClipVertex {
+    /// Interpolate between two vertices.
+    ///
+    /// `other`: the vertex to interpolate towards.
+    /// `t`: the interpolation factor in the range [0, 1].
+    ///
+    /// Returns the interpolated vertex.
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            clip: self.clip.lerp(&other.clip, t),
+            view: self.view.lerp(&other.view, t),
+            attributes: self.attributes.lerp(&other.attributes, t),
+        }
+    }
+}
+
+/// The geometry being transformed, either as independent triangles or as shared vertices referenced by index.
+#[derive(Clone, Debug)]
+enum Geometry<const N: usize, T: Clone> {
+    /// Independent triangles, each owning its vectors.
+    Triangles(Mesh<N, T>),
+    /// Shared vertices, each of which is only transformed once.
+    Indexed(IndexedMesh<N, T>),
+}
+
 /// A mesh transformation comprised of triangles with type `T` `N`-dimensional vectors.
+///
+/// Affine operations such as translation, scaling and rotation are accumulated into a single pending transformation, which is applied to the mesh's vectors in one pass once another stage needs them. Only `project` applies it as each vector is projected, fusing it with the projection. The stages that work on whole triangles, such as `normalize_filter`, `apply_luminance`, `apply_lights`, `clip_near` and `sort_by_depth`, first apply it to the whole mesh, so the fused path is only taken when `project` directly follows the affine operations. A transformation created from an indexed mesh applies pending transformations to each shared vertex once, and only expands into independent triangles when a stage works on whole triangles.
+///
+/// The pending transformation is held as an `f32` matrix, so affine operations and `mesh` are only available for `f32` vectors, as rotation and projection already were. Meshes with other vector types can be translated and scaled directly with `Mesh::translate` and `Mesh::scale`.
 #[derive(Clone, Debug)]
 pub struct Transform<const N: usize, T: Clone = VectorPointType> {
-    /// The triangles making up the mesh.
-    mesh: Mesh<N, T>,
+    /// The geometry making up the mesh.
+    geometry: Geometry<N, T>,
+    /// The affine transformation accumulated since the mesh was last updated, or the none variant if there is none.
+    pending: Option<Affine<N>>,
     /// The screen's projection matrix.
     projection_matrix: Matrix<4, 4, f32>,
+    /// The camera's view matrix.
+    view_matrix: Matrix<4, 4, f32>,
+    /// The kind of projection the camera uses.
+    projection: Projection,
+    /// The view near factor, used for clipping.
+    near: f32,
 }
 
 // Transformation implementation
@@ -67,85 +299,174 @@ impl<const N: usize, T: Clone> Transform<N, T> {
     /// Create a new mesh transformation.
     ///
     /// `mesh`: the mesh to transform.
-    /// `projection_matrix`: the projection matrix for the screen.
+    /// `camera`: the camera the mesh is viewed through.
+    /// `aspect_ratio`: the screen aspect ratio, as height divided by width.
+    ///
+    /// Returns the new mesh transformation.
+    pub fn new(mesh: &Mesh<N, T>, camera: &Camera, aspect_ratio: f32) -> Self {
+        Self::with_geometry(Geometry::Triangles(mesh.clone()), camera, aspect_ratio)
+    }
+
+    /// Create a new mesh transformation from an indexed mesh, so that each shared vertex is only transformed once.
+    ///
+    /// `mesh`: the indexed mesh to transform.
+    /// `camera`: the camera the mesh is viewed through.
+    /// `aspect_ratio`: the screen aspect ratio, as height divided by width.
     ///
     /// Returns the new mesh transformation.
-    pub fn new(mesh: &Mesh<N, T>, projection_matrix: &Matrix<4, 4, f32>) -> Self {
+    pub fn new_indexed(mesh: &IndexedMesh<N, T>, camera: &Camera, aspect_ratio: f32) -> Self {
+        Self::with_geometry(Geometry::Indexed(mesh.clone()), camera, aspect_ratio)
+    }
+
+    /// Create a new mesh transformation from some geometry.
+    ///
+    /// `geometry`: the geometry to transform.
+    /// `camera`: the camera the mesh is viewed through.
+    /// `aspect_ratio`: the screen aspect ratio, as height divided by width.
+    ///
+    /// Returns the new mesh transformation.
+    fn with_geometry(geometry: Geometry<N, T>, camera: &Camera, aspect_ratio: f32) -> Self {
         Self {
-            mesh: mesh.clone(),
-            projection_matrix: projection_matrix.clone(),
+            geometry,
+            pending: None,
+            projection_matrix: camera.projection_matrix(aspect_ratio),
+            view_matrix: camera.view_matrix(),
+            projection: camera.get_projection(),
+            near: camera.get_near(),
         }
     }
+}
 
-    /// Turn the transformation back into a mesh.
+// Affine transformation implementation
+impl<const N: usize> Transform<N, f32> {
+    /// Compose an affine transformation after any pending ones.
     ///
-    /// Returns the transformed mesh.
-    pub fn mesh(&self) -> Mesh<N, T> {
-        Mesh::from(self.mesh.clone())
+    /// `linear`: the linear part of the transformation.
+    /// `offset`: the offset applied after the linear part.
+    fn push_affine(&mut self, linear: &Matrix<N, N, f32>, offset: &Vector<N, f32>) {
+        let pending = self.pending.take().unwrap_or_else(Affine::identity);
+        self.pending = Some(pending.then(linear, offset));
+    }
+
+    /// Apply any pending affine transformation to the mesh's vectors. Surface normals are transformed by the normal matrix, so they stay perpendicular to the surface.
+    fn apply_pending(&mut self) {
+        if let Some(pending) = self.pending.take() {
+            let normal_matrix = pending.normal_matrix();
+            let transform_normal =
+                |matrix: &Matrix<3, 3, f32>, normal: &Vector<3>| (*matrix * *normal).normalize();
+
+            self.geometry = match &self.geometry {
+                Geometry::Triangles(mesh) => Geometry::Triangles(mesh.transform(|triangle| {
+                    let triangle = triangle.transform(|vector| pending.apply(vector));
+
+                    match &normal_matrix {
+                        Some(matrix) => {
+                            triangle.transform_normals(|normal| transform_normal(matrix, normal))
+                        }
+                        None => triangle,
+                    }
+                })),
+                Geometry::Indexed(mesh) => {
+                    let mesh = mesh.transform(|vector| pending.apply(vector));
+
+                    Geometry::Indexed(match &normal_matrix {
+                        Some(matrix) => {
+                            mesh.transform_normals(|normal| transform_normal(matrix, normal))
+                        }
+                        None => mesh,
+                    })
+                }
+            };
+        }
+    }
+
+    /// Apply any pending affine transformation and expand an indexed mesh into independent triangles, for stages that work on whole triangles.
+    ///
+    /// Returns a mutable reference to the triangles.
+    fn materialize(&mut self) -> &mut Mesh<N, f32> {
+        self.apply_pending();
+
+        if let Geometry::Indexed(mesh) = &self.geometry {
+            self.geometry = Geometry::Triangles(mesh.to_mesh());
+        }
+
+        match &mut self.geometry {
+            Geometry::Triangles(mesh) => mesh,
+            Geometry::Indexed(_) => unreachable!(),
+        }
     }
-}
 
-// Translation implementation
-impl<const N: usize, T: Clone + fmt::Debug + Add<Output = T>> Transform<N, T> {
     /// Translate the mesh.
     ///
     /// `values`: the amount by which to translate the mesh in each dimension.
     ///
     /// Returns the translated mesh transformation.
-    pub fn translate(&mut self, values: [T; N]) -> &mut Self {
-        self.mesh = self.mesh.transform(|triangle| {
-            triangle.transform(|vector| {
-                let mut new_vector = vector.clone();
-
-                for i in 0..N {
-                    new_vector[i] = vector[i].clone() + values[i].clone();
-                }
-
-                new_vector
-            })
-        });
+    pub fn translate(&mut self, values: [f32; N]) -> &mut Self {
+        self.push_affine(&Matrix::identity(), &Vector::from(values));
 
         self
     }
-}
 
-// Scaling implementation
-impl<const N: usize, T: Clone + fmt::Debug + Mul<Output = T>> Transform<N, T> {
     /// Scale the mesh.
     ///
     /// `factors`: the factors by which to scale the mesh in each dimension.
     ///
     /// Returns the scaled mesh transformation.
-    pub fn scale(&mut self, factors: [T; N]) -> &mut Self {
-        self.mesh = self.mesh.transform(|triangle| {
-            triangle.transform(|vector| {
-                let mut new_vector = vector.clone();
+    pub fn scale(&mut self, factors: [f32; N]) -> &mut Self {
+        let mut scale_matrix = Matrix::<N, N, f32>::fill(0.0);
 
-                for i in 0..N {
-                    new_vector[i] = vector[i].clone() * factors[i].clone();
-                }
+        for (i, factor) in factors.into_iter().enumerate() {
+            scale_matrix[(i, i)] = factor;
+        }
 
-                new_vector
-            })
-        });
+        self.push_affine(&scale_matrix, &Vector::from([0.0; N]));
 
         self
     }
+
+    /// Scale the mesh about a pivot point, which stays fixed in place.
+    ///
+    /// `pivot`: the point about which to scale the mesh.
+    /// `factors`: the factors by which to scale the mesh in each dimension.
+    ///
+    /// Returns the scaled mesh transformation.
+    pub fn scale_about<V: Into<Vector<N>>>(&mut self, pivot: V, factors: [f32; N]) -> &mut Self {
+        let pivot = *pivot.into().coords();
+
+        self.translate(pivot.map(|value| -value))
+            .scale(factors)
+            .translate(pivot)
+    }
+
+    /// Turn the transformation back into a mesh, applying any pending transformation.
+    ///
+    /// Returns the transformed mesh.
+    pub fn mesh(&self) -> Mesh<N, f32> {
+        match (&self.geometry, &self.pending) {
+            (Geometry::Triangles(mesh), None) => mesh.clone(),
+            (Geometry::Indexed(mesh), None) => mesh.to_mesh(),
+            _ => self.clone().materialize().clone(),
+        }
+    }
 }
 
 // Rotation and projection implementation
 impl Transform<3, f32> {
+    /// Compose an affine 4x4 matrix after any pending transformations.
+    ///
+    /// `matrix`: the affine matrix.
+    fn push_matrix(&mut self, matrix: &Matrix<4, 4, f32>) {
+        let affine = Affine::from_matrix(matrix);
+        self.push_affine(&affine.linear, &affine.offset);
+    }
+
     /// Rotate the mesh about the x-axis.
     ///
     /// `angle`: the angle of rotation in radians around the x-axis.
     ///
     /// Returns the rotated mesh transformation.
     pub fn rotate_x(&mut self, angle: f32) -> &mut Self {
-        let x_projection_matrix = generate_x_rotation_matrix(angle);
-
-        self.mesh = self
-            .mesh
-            .transform(|triangle| triangle.transform(|&vector| x_projection_matrix * vector));
+        self.push_matrix(&generate_x_rotation_matrix(angle));
 
         self
     }
@@ -156,11 +477,7 @@ impl Transform<3, f32> {
     ///
     /// Returns the rotated mesh transformation.
     pub fn rotate_y(&mut self, angle: f32) -> &mut Self {
-        let y_projection_matrix = generate_y_rotation_matrix(angle);
-
-        self.mesh = self
-            .mesh
-            .transform(|triangle| triangle.transform(|&vector| y_projection_matrix * vector));
+        self.push_matrix(&generate_y_rotation_matrix(angle));
 
         self
     }
@@ -171,80 +488,309 @@ impl Transform<3, f32> {
     ///
     /// Returns the rotated mesh transformation.
     pub fn rotate_z(&mut self, angle: f32) -> &mut Self {
-        let z_projection_matrix = generate_z_rotation_matrix(angle);
+        self.push_matrix(&generate_z_rotation_matrix(angle));
 
-        self.mesh = self
-            .mesh
-            .transform(|triangle| triangle.transform(|&vector| z_projection_matrix * vector));
+        self
+    }
+
+    /// Rotate the mesh about an arbitrary axis through the origin.
+    ///
+    /// `axis`: the axis of rotation, which does not need to be normalized.
+    /// `angle`: the angle of rotation in radians around the axis, following the right-hand rule.
+    ///
+    /// Returns the rotated mesh transformation.
+    pub fn rotate_axis<V: Into<Vector<3>>>(&mut self, axis: V, angle: f32) -> &mut Self {
+        self.push_matrix(&generate_axis_rotation_matrix(&axis.into(), angle));
 
         self
     }
 
-    /// Normalize and filter out all triangles that cannot be seen. This should be called after all other transformations and before projection.
+    /// Rotate the mesh about an arbitrary axis through a pivot point, such as the mesh's centroid.
+    ///
+    /// `pivot`: the point through which the axis of rotation passes.
+    /// `axis`: the direction of the axis of rotation, which does not need to be normalized.
+    /// `angle`: the angle of rotation in radians around the axis, following the right-hand rule.
     ///
-    /// `camera`: the camera.
+    /// Returns the rotated mesh transformation.
+    pub fn rotate_about<P: Into<Vector<3>>, A: Into<Vector<3>>>(
+        &mut self,
+        pivot: P,
+        axis: A,
+        angle: f32,
+    ) -> &mut Self {
+        let pivot = *pivot.into().coords();
+
+        self.translate(pivot.map(|value| -value))
+            .rotate_axis(axis, angle)
+            .translate(pivot)
+    }
+
+    /// Rotate the mesh by a quaternion.
+    ///
+    /// `rotation`: the rotation quaternion, which should be normalized.
+    ///
+    /// Returns the rotated mesh transformation.
+    pub fn rotate(&mut self, rotation: &Quaternion) -> &mut Self {
+        self.push_matrix(&rotation.to_matrix());
+
+        self
+    }
+
+    /// Transform the mesh by an affine matrix, such as a scene node's world transform. Any projective part of the matrix is ignored.
+    ///
+    /// `matrix`: the affine 4x4 matrix.
+    ///
+    /// Returns the transformed mesh transformation.
+    pub fn apply_matrix(&mut self, matrix: &Matrix<4, 4, f32>) -> &mut Self {
+        self.push_matrix(matrix);
+
+        self
+    }
+
+    /// Move the mesh from world space into the camera's view space, where the camera sits at the origin facing down the positive z-axis. This should be called after all model transformations.
+    ///
+    /// Returns the viewed mesh transformation.
+    pub fn view(&mut self) -> &mut Self {
+        let view_matrix = self.view_matrix;
+        self.push_matrix(&view_matrix);
+
+        self
+    }
+
+    /// Normalize and filter out all triangles facing away from the camera. This should be called after the view transformation and before projection.
+    ///
+    /// With perspective projection, triangles are tested against the direction from the camera to the triangle. With orthographic projection, every triangle is tested against the constant view direction.
     ///
     /// Returns the filtered mesh transformation.
-    pub fn normalize_filter(&mut self, camera: &Vector<3, f32>) -> &mut Self {
-        self.mesh = self
-            .mesh
+    pub fn normalize_filter(&mut self) -> &mut Self {
+        let projection = self.projection;
+        let mesh = self.materialize();
+
+        *mesh = mesh
             .triangles()
-            .to_owned()
-            .into_iter()
-            .filter(|triangle| triangle.normal().dot(&(triangle[0] - *camera)) < 0.0)
+            .iter()
+            .filter(|triangle| match projection {
+                Projection::Perspective => triangle.normal().dot(&triangle[0]) < 0.0,
+                Projection::Orthographic { .. } => triangle.normal()[2] < 0.0,
+            })
+            .copied()
             .collect();
 
         self
     }
 
-    /// Apply luminance to the triangles within the mesh.
+    /// Apply luminance to the triangles within the mesh. Triangles with surface normals are lit at each of their vectors, so the luminance is smoothly interpolated across them when filled, while triangles without are lit by their face normal.
     ///
     /// `light`: the position of the light source.
     ///
     /// Returns the resulting mesh transformation.
     pub fn apply_luminance(&mut self, light: &Vector<3, f32>) -> &mut Self {
-        self.mesh = self.mesh.transform(|triangle| {
+        let mesh = self.materialize();
+
+        *mesh = mesh.transform(|triangle| {
             let mut new_triangle = triangle.to_owned();
-            new_triangle.set_luminance(triangle.normal().dot(light));
+
+            match triangle.get_normals() {
+                Some(normals) => {
+                    new_triangle.set_luminances(normals.map(|normal| normal.normalize().dot(light)))
+                }
+                None => new_triangle.set_luminance(triangle.normal().dot(light)),
+            }
+
             new_triangle
         });
 
         self
     }
 
-    /// Project the mesh transformation from 3D space into 2D space.
+    /// Light the triangles within the mesh with colored light, adding up the light received from each source. Triangles with surface normals are lit at each of their vectors, so the light is smoothly interpolated across them when filled, while triangles without are lit at their centroid by their face normal. The colored light takes the place of any luminance when the triangles are filled.
+    ///
+    /// Lights must be in the same space as the mesh, which is view space when this is called after the view transformation. The light calculated here is not used by `Shading::PerPixel`, which lights each pixel with its own lights instead.
+    ///
+    /// `lights`: the light sources.
+    ///
+    /// Returns the resulting mesh transformation.
+    pub fn apply_lights(&mut self, lights: &[Light]) -> &mut Self {
+        let mesh = self.materialize();
+        let illuminate = |position: &Vector<3>, normal: &Vector<3>| {
+            lights.iter().fold(Vector::from([0.0; 3]), |total, light| {
+                total + light.illuminate(position, normal)
+            })
+        };
+
+        *mesh = mesh.transform(|triangle| {
+            let mut new_triangle = triangle.to_owned();
+
+            let illumination = match triangle.get_normals() {
+                Some(normals) => {
+                    [0, 1, 2].map(|i| illuminate(&triangle[i], &normals[i].normalize()))
+                }
+                None => {
+                    let centroid = (triangle[0] + triangle[1] + triangle[2]) / 3.0;
+                    [illuminate(&centroid, &triangle.normal()); 3]
+                }
+            };
+
+            new_triangle.set_illumination(Some(illumination));
+            new_triangle
+        });
+
+        self
+    }
+
+    /// Clip the triangles within the mesh against the near plane. Triangles lying entirely on the camera side of the near plane are discarded, and triangles crossing it are split into one or two new triangles. This should be called after all other transformations and before projection.
+    ///
+    /// Returns the clipped mesh transformation.
+    pub fn clip_near(&mut self) -> &mut Self {
+        let near = self.near;
+        let mesh = self.materialize();
+
+        *mesh = mesh
+            .triangles()
+            .iter()
+            .flat_map(|triangle| {
+                // Each vertex carries its attributes so they can be interpolated at the new edges
+                let polygon = VertexAttributes::from_triangle(triangle)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, attributes)| (triangle[i], attributes))
+                    .collect::<Vec<_>>();
+
+                let polygon = clip_polygon(
+                    &polygon,
+                    |(vec, _)| vec[2] - near,
+                    |(a, a_attributes), (b, b_attributes), t| {
+                        (a.lerp(b, t), a_attributes.lerp(b_attributes, t))
+                    },
+                );
+
+                triangulate_fan(&polygon).into_iter().map(|vertices| {
+                    let mut clipped = Triangle::new(vertices.map(|(vec, _)| vec), 1.0);
+                    VertexAttributes::apply(
+                        vertices.map(|(_, attributes)| attributes),
+                        &mut clipped,
+                    );
+                    clipped
+                })
+            })
+            .collect();
+
+        self
+    }
+
+    /// Sort the triangles within the mesh from back to front by their average depth, so that filling them in order paints nearer triangles over farther ones. This is a cheap alternative to depth testing, and should be called after all other transformations and before projection.
+    ///
+    /// Returns the sorted mesh transformation.
+    pub fn sort_by_depth(&mut self) -> &mut Self {
+        let average_depth =
+            |triangle: &Triangle<3, f32>| (triangle[0][2] + triangle[1][2] + triangle[2][2]) / 3.0;
+
+        let mesh = self.materialize();
+        let mut tris = mesh.triangles().to_vec();
+        tris.sort_by(|a, b| average_depth(b).total_cmp(&average_depth(a)));
+        *mesh = Mesh::new(tris);
+
+        self
+    }
+
+    /// Project the mesh transformation from 3D space into 2D space. Any transformation still pending, which is the case when no stage working on whole triangles has been called since the last affine operation, is applied as each vector is projected without first updating the whole mesh. Each vector of an indexed mesh is only projected once.
+    ///
+    /// Vectors are first moved into homogeneous clip space, where triangles are clipped against the near plane. The perspective divide is only performed afterwards, so no vector is divided by a zero or negative w component. The projected depth of each vector is kept on the resulting triangles for depth testing, along with its view space position and normal for per-pixel lighting.
     ///
     /// Returns the projected mesh transformation.
     pub fn project(&self) -> Transform<2, f32> {
-        let projected_tris = self
-            .mesh
-            .triangles()
+        let normal_matrix = self.pending.as_ref().and_then(Affine::normal_matrix);
+
+        let vertex = |vec: &Vector<3>, attributes: VertexAttributes| {
+            let view = match &self.pending {
+                Some(pending) => pending.apply(vec),
+                None => *vec,
+            };
+
+            ClipVertex {
+                clip: self.projection_matrix * view.extend(1.0),
+                view,
+                attributes: VertexAttributes {
+                    normal: attributes.normal.map(|normal| match &normal_matrix {
+                        Some(matrix) => (*matrix * normal).normalize(),
+                        None => normal,
+                    }),
+                    ..attributes
+                },
+            }
+        };
+
+        // Clip space stage, where the near plane lies at a z component of zero
+        let clip_tris: Vec<[ClipVertex; 3]> = match &self.geometry {
+            Geometry::Triangles(mesh) => mesh
+                .triangles()
+                .iter()
+                .map(|triangle| {
+                    let attributes = VertexAttributes::from_triangle(triangle);
+
+                    [0, 1, 2].map(|i| vertex(&triangle[i], attributes[i]))
+                })
+                .collect(),
+            Geometry::Indexed(mesh) => {
+                let normals = mesh.get_normals();
+                let clip_vertices: Vec<_> = mesh
+                    .vertices()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, vec)| {
+                        let attributes = VertexAttributes {
+                            lum: 1.0,
+                            normal: normals.map(|normals| normals[i]),
+                            illumination: None,
+                        };
+
+                        vertex(vec, attributes)
+                    })
+                    .collect();
+
+                mesh.indices()
+                    .iter()
+                    .map(|corners| corners.map(|index| clip_vertices[index]))
+                    .collect()
+            }
+        };
+
+        let projected_tris = clip_tris
             .iter()
-            .map(|triangle| {
-                Triangle::new(
-                    triangle
-                        .vectors()
-                        .iter()
-                        .map(|&vec| self.projection_matrix * vec)
-                        .map(|vec| Vector::from([vec[0], vec[1]]))
-                        .collect::<Vec<Vector<2>>>()
-                        .try_into()
-                        .unwrap(),
-                    triangle.get_luminance(),
-                )
+            .flat_map(|clip_vertices| {
+                let polygon =
+                    clip_polygon(clip_vertices, |vertex| vertex.clip[2], ClipVertex::lerp);
+
+                triangulate_fan(&polygon).into_iter().map(|vertices| {
+                    let projected = vertices.map(|vertex| vertex.clip.perspective_divide());
+
+                    let mut projected_triangle =
+                        Triangle::new(projected.map(|vec| Vector::from([vec[0], vec[1]])), 1.0);
+                    VertexAttributes::apply(
+                        vertices.map(|vertex| vertex.attributes),
+                        &mut projected_triangle,
+                    );
+                    projected_triangle.set_depths(projected.map(|vec| vec[2]));
+                    projected_triangle.set_view_positions(Some(vertices.map(|vertex| vertex.view)));
+                    projected_triangle
+                })
             })
             .collect::<Vec<_>>();
 
         Transform {
-            mesh: Mesh::from(projected_tris),
-            projection_matrix: self.projection_matrix.clone(),
+            geometry: Geometry::Triangles(Mesh::from(projected_tris)),
+            pending: None,
+            projection_matrix: self.projection_matrix,
+            view_matrix: self.view_matrix,
+            projection: self.projection,
+            near: self.near,
         }
     }
 }
 
 // Convert the mesh transformation back into a mesh.
-impl<const N: usize, T: Clone + fmt::Debug + Add<Output = T>> Into<Mesh<N, T>> for Transform<N, T> {
-    fn into(self) -> Mesh<N, T> {
-        self.mesh()
+impl<const N: usize> From<Transform<N, f32>> for Mesh<N, f32> {
+    fn from(transform: Transform<N, f32>) -> Self {
+        transform.mesh()
     }
 }
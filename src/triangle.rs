@@ -7,8 +7,16 @@ use std::ops::{Index, IndexMut};
 pub struct Triangle<const N: usize, T: Clone = VectorPointType> {
     /// The vectors making up the triangle.
     vecs: [Vector<N, T>; 3],
-    /// The luminance of the triangle.
-    lum: f32,
+    /// The luminance at each of the triangle's vectors, interpolated across the triangle when it is filled.
+    lum: [f32; 3],
+    /// The depth of each of the triangle's vectors, carried through projection.
+    depths: [f32; 3],
+    /// The surface normal at each of the triangle's vectors, or the none variant if the triangle is flat shaded.
+    normals: Option<[Vector<3, T>; 3]>,
+    /// The view space position of each of the triangle's vectors, carried through projection for per-pixel lighting.
+    view_positions: Option<[Vector<3, T>; 3]>,
+    /// The red, green and blue light received at each of the triangle's vectors, or the none variant if the triangle is lit by its luminance.
+    illumination: Option<[Vector<3>; 3]>,
 }
 
 // Triangle implementation
@@ -16,11 +24,18 @@ impl<const N: usize, T: Clone> Triangle<N, T> {
     /// Create a new triangle from three vectors.
     ///
     /// `vecs`: an array of the vectors.
-    /// `lum`: the luminance value.
+    /// `lum`: the luminance value, shared by every vector.
     ///
     /// Returns the new triangle.
     pub fn new(vecs: [Vector<N, T>; 3], lum: f32) -> Self {
-        Self { vecs, lum }
+        Self {
+            vecs,
+            lum: [lum; 3],
+            depths: [0.0; 3],
+            normals: None,
+            view_positions: None,
+            illumination: None,
+        }
     }
 
     /// Get the vectors making up the triangle.
@@ -30,20 +45,90 @@ impl<const N: usize, T: Clone> Triangle<N, T> {
         self.vecs.as_slice().try_into().unwrap()
     }
 
-    /// Get the luminance of the triangle.
+    /// Get the luminance of the triangle, averaged over its vectors.
     ///
     /// Returns the triangle's luminance value.
     pub fn get_luminance(&self) -> f32 {
-        self.lum
+        (self.lum[0] + self.lum[1] + self.lum[2]) / 3.0
     }
 
-    /// Set the luminance of the triangle.
+    /// Set the luminance of the whole triangle.
     ///
     /// `lum`: the luminance value.
     pub fn set_luminance(&mut self, lum: f32) {
+        self.lum = [lum; 3];
+    }
+
+    /// Get the luminance at each of the triangle's vectors.
+    ///
+    /// Returns the luminance of each vector.
+    pub fn get_luminances(&self) -> [f32; 3] {
+        self.lum
+    }
+
+    /// Set the luminance at each of the triangle's vectors, so that it is smoothly interpolated across the triangle.
+    ///
+    /// `lum`: the luminance of each vector.
+    pub fn set_luminances(&mut self, lum: [f32; 3]) {
         self.lum = lum;
     }
 
+    /// Get the colored light received at each of the triangle's vectors.
+    ///
+    /// Returns an option containing the red, green and blue light of each vector, or the none variant if the triangle is lit by its luminance.
+    pub fn get_illumination(&self) -> Option<&[Vector<3>; 3]> {
+        self.illumination.as_ref()
+    }
+
+    /// Set the colored light received at each of the triangle's vectors, which takes the place of the luminance and is interpolated across the triangle when it is filled.
+    ///
+    /// `illumination`: the red, green and blue light of each vector, where one leaves a color unchanged, or the none variant to light the triangle by its luminance.
+    pub fn set_illumination(&mut self, illumination: Option<[Vector<3>; 3]>) {
+        self.illumination = illumination;
+    }
+
+    /// Get the depths of the triangle's vectors.
+    ///
+    /// Returns the depth of each vector.
+    pub fn get_depths(&self) -> [f32; 3] {
+        self.depths
+    }
+
+    /// Set the depths of the triangle's vectors.
+    ///
+    /// `depths`: the depth of each vector.
+    pub fn set_depths(&mut self, depths: [f32; 3]) {
+        self.depths = depths;
+    }
+
+    /// Get the surface normals at the triangle's vectors.
+    ///
+    /// Returns an option containing the normal of each vector, or the none variant if the triangle is flat shaded.
+    pub fn get_normals(&self) -> Option<&[Vector<3, T>; 3]> {
+        self.normals.as_ref()
+    }
+
+    /// Set the surface normals at the triangle's vectors.
+    ///
+    /// `normals`: the normal of each vector, or the none variant to flat shade the triangle.
+    pub fn set_normals(&mut self, normals: Option<[Vector<3, T>; 3]>) {
+        self.normals = normals;
+    }
+
+    /// Get the view space positions of the triangle's vectors.
+    ///
+    /// Returns an option containing the view space position of each vector, or the none variant if the triangle has not been projected.
+    pub fn get_view_positions(&self) -> Option<&[Vector<3, T>; 3]> {
+        self.view_positions.as_ref()
+    }
+
+    /// Set the view space positions of the triangle's vectors.
+    ///
+    /// `view_positions`: the view space position of each vector, or the none variant to remove them.
+    pub fn set_view_positions(&mut self, view_positions: Option<[Vector<3, T>; 3]>) {
+        self.view_positions = view_positions;
+    }
+
     /// Get a reference to one of the vectors making up the triangle.
     ///
     /// `index`: the index of the vector.
@@ -69,8 +154,8 @@ impl Triangle<3, f32> {
     ///
     /// Returns the calculated normal.
     pub fn normal(&self) -> Vector<3, f32> {
-        let a = self.vecs[1].clone() - self.vecs[0].clone();
-        let b = self.vecs[2].clone() - self.vecs[0].clone();
+        let a = self.vecs[1] - self.vecs[0];
+        let b = self.vecs[2] - self.vecs[0];
 
         a.cross(&b).normalize()
     }
@@ -96,8 +181,30 @@ impl<const N: usize, T: Clone + fmt::Debug> Triangle<N, T> {
                 .try_into()
                 .unwrap(),
             lum: self.lum,
+            depths: self.depths,
+            normals: self.normals.clone(),
+            view_positions: self.view_positions.clone(),
+            illumination: self.illumination,
         }
     }
+
+    /// Transform each of the triangle's surface normals, if it has any.
+    ///
+    /// `f`: the closure used to map each normal.
+    ///
+    /// Returns the resulting triangle.
+    pub fn transform_normals<F>(&self, f: F) -> Self
+    where
+        F: FnMut(&Vector<3, T>) -> Vector<3, T>,
+    {
+        let mut triangle = self.clone();
+        triangle.normals = self
+            .normals
+            .as_ref()
+            .map(|normals| normals.each_ref().map(f));
+
+        triangle
+    }
 }
 
 // Indexing operations for triangles
@@ -105,7 +212,7 @@ impl<const N: usize, T: Clone> Index<usize> for Triangle<N, T> {
     type Output = Vector<N, T>;
 
     fn index(&self, index: usize) -> &Self::Output {
-        &self.get(index).unwrap()
+        self.get(index).unwrap()
     }
 }
 
@@ -121,7 +228,11 @@ impl<const N: usize, T: Clone, V: Into<Vector<N, T>>> From<(V, V, V)> for Triang
     fn from((x, y, z): (V, V, V)) -> Self {
         Self {
             vecs: [x.into(), y.into(), z.into()],
-            lum: 1.0,
+            lum: [1.0; 3],
+            depths: [0.0; 3],
+            normals: None,
+            view_positions: None,
+            illumination: None,
         }
     }
 }
@@ -137,7 +248,11 @@ impl<const N: usize, T: Clone + fmt::Debug> FromIterator<Vector<N, T>> for Trian
 
         Self {
             vecs: vecs.try_into().unwrap(),
-            lum: 1.0,
+            lum: [1.0; 3],
+            depths: [0.0; 3],
+            normals: None,
+            view_positions: None,
+            illumination: None,
         }
     }
 }
@@ -1,12 +1,14 @@
 use std::fmt;
 use std::iter::Sum;
-use std::ops::{Add, Index, IndexMut, Mul, Sub};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
 
 /// The default type for a vector point.
 pub type VectorPointType = f32;
 
 /// A vector with coordinates of type `T` in `N`-dimensions.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Vector<const N: usize, T: Clone = VectorPointType> {
     /// The vector's coordinates.
     coordinates: [T; N],
@@ -84,6 +86,50 @@ impl<T: Clone + Sub<Output = T> + Mul<Output = T>> Vector<3, T> {
     }
 }
 
+// Homogeneous coordinate conversions
+impl<T: Clone> Vector<3, T> {
+    /// Extend the vector into four dimensions, as homogeneous coordinates.
+    ///
+    /// `w`: the value of the fourth coordinate, usually one for points and zero for directions.
+    ///
+    /// Returns the extended vector.
+    pub fn extend(&self, w: T) -> Vector<4, T> {
+        let [x, y, z] = self.coordinates.clone();
+
+        Vector {
+            coordinates: [x, y, z, w],
+        }
+    }
+}
+
+// Homogeneous coordinate conversions
+impl<T: Clone> Vector<4, T> {
+    /// Drop the fourth coordinate of the vector, without dividing by it.
+    ///
+    /// Returns the truncated vector.
+    pub fn truncate(&self) -> Vector<3, T> {
+        let [x, y, z, _] = self.coordinates.clone();
+
+        Vector {
+            coordinates: [x, y, z],
+        }
+    }
+}
+
+// Perspective division
+impl Vector<4, f32> {
+    /// Perform the perspective divide, dividing the first three coordinates of the vector by the fourth.
+    ///
+    /// Returns the resulting vector in three dimensions.
+    pub fn perspective_divide(&self) -> Vector<3, f32> {
+        let [x, y, z, w] = self.coordinates;
+
+        Vector {
+            coordinates: [x / w, y / w, z / w],
+        }
+    }
+}
+
 // Vector normalization
 impl<const N: usize> Vector<N, f32> {
     /// Normalize the vector.
@@ -112,18 +158,174 @@ impl<const N: usize, T: Clone + Mul<Output = T> + Sum> Vector<N, T> {
     /// Returns the resulting dot product.
     pub fn dot(&self, other: &Self) -> T {
         (0..N)
-            .into_iter()
             .map(|i| self.coordinates[i].clone() * other.coordinates[i].clone())
             .sum()
     }
 }
 
+// Component-wise vector operations
+impl<const N: usize, T: Clone> Vector<N, T> {
+    /// Combine two vectors component by component.
+    ///
+    /// `other`: the other vector.
+    /// `f`: the closure used to combine each pair of values.
+    ///
+    /// Returns the resulting vector.
+    fn zip_with<F>(&self, other: &Self, mut f: F) -> Self
+    where
+        F: FnMut(T, T) -> T,
+    {
+        Self {
+            coordinates: std::array::from_fn(|i| {
+                f(self.coordinates[i].clone(), other.coordinates[i].clone())
+            }),
+        }
+    }
+
+    /// Multiply two vectors component by component.
+    ///
+    /// `other`: the other vector.
+    ///
+    /// Returns the resulting vector.
+    pub fn component_mul(&self, other: &Self) -> Self
+    where
+        T: Mul<Output = T>,
+    {
+        self.zip_with(other, |a, b| a * b)
+    }
+
+    /// Divide two vectors component by component.
+    ///
+    /// `other`: the other vector.
+    ///
+    /// Returns the resulting vector.
+    pub fn component_div(&self, other: &Self) -> Self
+    where
+        T: Div<Output = T>,
+    {
+        self.zip_with(other, |a, b| a / b)
+    }
+
+    /// Take the smaller value of two vectors in each dimension.
+    ///
+    /// `other`: the other vector.
+    ///
+    /// Returns the component-wise minimum vector.
+    pub fn min(&self, other: &Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        self.zip_with(other, |a, b| if b < a { b } else { a })
+    }
+
+    /// Take the larger value of two vectors in each dimension.
+    ///
+    /// `other`: the other vector.
+    ///
+    /// Returns the component-wise maximum vector.
+    pub fn max(&self, other: &Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        self.zip_with(other, |a, b| if b > a { b } else { a })
+    }
+
+    /// Restrict each value of the vector to a range.
+    ///
+    /// `min`: the vector of lower bounds.
+    /// `max`: the vector of upper bounds.
+    ///
+    /// Returns the clamped vector.
+    pub fn clamp(&self, min: &Self, max: &Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        self.max(min).min(max)
+    }
+
+    /// Compute the squared length of the vector.
+    ///
+    /// Returns the squared length.
+    pub fn length_squared(&self) -> T
+    where
+        T: Mul<Output = T> + Sum,
+    {
+        self.dot(self)
+    }
+}
+
+// Floating point vector geometry
+impl<const N: usize> Vector<N, f32> {
+    /// Compute the length of the vector.
+    ///
+    /// Returns the length.
+    pub fn length(&self) -> f32 {
+        self.length_squared().sqrt()
+    }
+
+    /// Compute the squared distance between two points.
+    ///
+    /// `other`: the other point.
+    ///
+    /// Returns the squared distance.
+    pub fn distance_squared(&self, other: &Self) -> f32 {
+        (*self - *other).length_squared()
+    }
+
+    /// Compute the distance between two points.
+    ///
+    /// `other`: the other point.
+    ///
+    /// Returns the distance.
+    pub fn distance(&self, other: &Self) -> f32 {
+        (*self - *other).length()
+    }
+
+    /// Linearly interpolate between two vectors.
+    ///
+    /// `other`: the vector to interpolate towards.
+    /// `t`: the interpolation factor, where zero gives this vector and one gives the other vector.
+    ///
+    /// Returns the interpolated vector.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        *self + (*other - *self) * t
+    }
+
+    /// Reflect the vector off a surface.
+    ///
+    /// `normal`: the normalized surface normal.
+    ///
+    /// Returns the reflected vector.
+    pub fn reflect(&self, normal: &Self) -> Self {
+        *self - *normal * (2.0 * self.dot(normal))
+    }
+
+    /// Project the vector onto another vector.
+    ///
+    /// `other`: the vector onto which to project.
+    ///
+    /// Returns the component of this vector parallel to the other vector.
+    pub fn project_onto(&self, other: &Self) -> Self {
+        *other * (self.dot(other) / other.length_squared())
+    }
+
+    /// Check whether two vectors are equal within a tolerance in each dimension.
+    ///
+    /// `other`: the other vector.
+    /// `epsilon`: the largest allowed difference in any dimension.
+    ///
+    /// Returns whether the vectors are approximately equal.
+    pub fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        (0..N).all(|i| (self.coordinates[i] - other.coordinates[i]).abs() <= epsilon)
+    }
+}
+
 // Indexing operations for vectors
 impl<const N: usize, T: Clone> Index<usize> for Vector<N, T> {
     type Output = T;
 
     fn index(&self, index: usize) -> &Self::Output {
-        &self.get(index).unwrap()
+        self.get(index).unwrap()
     }
 }
 
@@ -153,7 +355,6 @@ impl<const N: usize, T: Clone + fmt::Debug + Add<Output = T>> Add for Vector<N,
     fn add(self, rhs: Self) -> Self::Output {
         Self {
             coordinates: (0..self.coordinates.len())
-                .into_iter()
                 .map(|i| self.coordinates[i].clone() + rhs.coordinates[i].clone())
                 .collect::<Vec<_>>()
                 .try_into()
@@ -169,7 +370,6 @@ impl<const N: usize, T: Clone + fmt::Debug + Sub<Output = T>> Sub for Vector<N,
     fn sub(self, rhs: Self) -> Self::Output {
         Self {
             coordinates: (0..self.coordinates.len())
-                .into_iter()
                 .map(|i| self.coordinates[i].clone() - rhs.coordinates[i].clone())
                 .collect::<Vec<_>>()
                 .try_into()
@@ -177,3 +377,73 @@ impl<const N: usize, T: Clone + fmt::Debug + Sub<Output = T>> Sub for Vector<N,
         }
     }
 }
+
+// Add vectors in place
+impl<const N: usize, T: Clone + Add<Output = T>> AddAssign for Vector<N, T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.zip_with(&rhs, |a, b| a + b);
+    }
+}
+
+// Subtract vectors in place
+impl<const N: usize, T: Clone + Sub<Output = T>> SubAssign for Vector<N, T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.zip_with(&rhs, |a, b| a - b);
+    }
+}
+
+// Negate vectors
+impl<const N: usize, T: Clone + Neg<Output = T>> Neg for Vector<N, T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            coordinates: self.coordinates.map(|value| -value),
+        }
+    }
+}
+
+// Multiply vectors by a scalar
+impl<const N: usize, T: Clone + Mul<Output = T>> Mul<T> for Vector<N, T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            coordinates: self.coordinates.map(|value| value * rhs.clone()),
+        }
+    }
+}
+
+// Multiply a scalar by a vector
+impl<const N: usize> Mul<Vector<N, f32>> for f32 {
+    type Output = Vector<N, f32>;
+
+    fn mul(self, rhs: Vector<N, f32>) -> Self::Output {
+        rhs * self
+    }
+}
+
+// Divide vectors by a scalar
+impl<const N: usize, T: Clone + Div<Output = T>> Div<T> for Vector<N, T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Self {
+            coordinates: self.coordinates.map(|value| value / rhs.clone()),
+        }
+    }
+}
+
+// Multiply vectors by a scalar in place
+impl<const N: usize, T: Clone + Mul<Output = T>> MulAssign<T> for Vector<N, T> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = self.clone() * rhs;
+    }
+}
+
+// Divide vectors by a scalar in place
+impl<const N: usize, T: Clone + Div<Output = T>> DivAssign<T> for Vector<N, T> {
+    fn div_assign(&mut self, rhs: T) {
+        *self = self.clone() / rhs;
+    }
+}